@@ -0,0 +1,368 @@
+//! Wrap-around arithmetic on the 14-bit angle range
+
+use core::ops::{Add, Sub};
+
+use crate::{driver::ANGLE_MAX, protocol::DATA_MASK, utils};
+
+/// Signed shortest-arc difference from `from` to `to` in counts, in the
+/// range `-8192..8192`
+///
+/// A difference of exactly half a revolution is reported as `-8192`
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::angle::shortest_delta;
+///
+/// assert_eq!(shortest_delta(16383, 0), 1);
+/// assert_eq!(shortest_delta(0, 16383), -1);
+/// assert_eq!(shortest_delta(0, 8191), 8191);
+/// assert_eq!(shortest_delta(0, 8192), -8192);
+/// assert_eq!(shortest_delta(0, 8193), -8191);
+/// ```
+#[must_use]
+pub fn shortest_delta(from: u16, to: u16) -> i16 {
+    // Shift the 14-bit difference into the top of the word so the arithmetic
+    // shift back down sign-extends it
+    #[allow(clippy::cast_possible_wrap)]
+    let delta = (to.wrapping_sub(from) << 2) as i16 >> 2;
+    delta
+}
+
+/// Add a signed number of counts to an angle, wrapping into `0..16384`
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::angle::wrap_add;
+///
+/// assert_eq!(wrap_add(16383, 1), 0);
+/// assert_eq!(wrap_add(0, -1), 16383);
+/// assert_eq!(wrap_add(100, 8192), 8292);
+/// assert_eq!(wrap_add(100, -16384 * 3), 100);
+/// ```
+#[must_use]
+pub fn wrap_add(angle: u16, delta: i32) -> u16 {
+    let angle = i32::from(angle & DATA_MASK) + delta.rem_euclid(i32::from(ANGLE_MAX));
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let angle = angle as u16 & DATA_MASK;
+    angle
+}
+
+/// Scale an angle onto the range `0..full_scale`, computed as
+/// `angle * full_scale / 16384` and rounded down
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::angle::scale;
+///
+/// assert_eq!(scale(0, 1000), 0);
+/// assert_eq!(scale(8192, 1000), 500);
+/// assert_eq!(scale(16383, 1000), 999);
+/// ```
+#[must_use]
+pub fn scale(angle: u16, full_scale: u32) -> u32 {
+    let scaled = u64::from(angle & DATA_MASK) * u64::from(full_scale) / u64::from(ANGLE_MAX);
+    #[allow(clippy::cast_possible_truncation)]
+    let scaled = scaled as u32;
+    scaled
+}
+
+/// Interpolate between two angles along the shortest arc from `a` to `b`
+///
+/// `t` is clamped to `[0, 1]`, with 0 giving `a` and 1 giving `b`. The result
+/// is rounded to the nearest count. For angles exactly half a revolution
+/// apart, the arc runs in the direction of decreasing counts, matching
+/// [`shortest_delta`]
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::angle::lerp_angle;
+///
+/// assert_eq!(lerp_angle(100, 200, 0.5), 150);
+/// assert_eq!(lerp_angle(16300, 100, 0.5), 8);
+/// assert_eq!(lerp_angle(16300, 100, 1.0), 100);
+///
+/// // Half a revolution apart: the arc runs through decreasing counts
+/// assert_eq!(lerp_angle(8192, 0, 0.5), 4096);
+/// assert_eq!(lerp_angle(0, 8192, 0.5), 12288);
+/// ```
+#[cfg(feature = "float")]
+#[must_use]
+pub fn lerp_angle(a: u16, b: u16, t: f32) -> u16 {
+    let offset = f32::from(shortest_delta(a, b)) * t.clamp(0.0, 1.0);
+    let offset = if offset >= 0.0 {
+        offset + 0.5
+    } else {
+        offset - 0.5
+    };
+
+    #[allow(clippy::cast_possible_truncation)]
+    let angle = wrap_add(a, offset as i32);
+    angle
+}
+
+/// Convert an angle to whole degrees in the range `-180..180`, rounded
+/// down
+///
+/// Angles below half a revolution map to `0..180` and the rest to
+/// `-180..0`, so the sign always matches the half of the revolution
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::angle::signed_degrees;
+///
+/// assert_eq!(signed_degrees(0), 0);
+/// assert_eq!(signed_degrees(4096), 90);
+/// assert_eq!(signed_degrees(8191), 179);
+/// assert_eq!(signed_degrees(8192), -180);
+/// assert_eq!(signed_degrees(12288), -90);
+/// assert_eq!(signed_degrees(16383), -1);
+/// ```
+#[must_use]
+pub fn signed_degrees(angle: u16) -> i16 {
+    let counts = i32::from(shortest_delta(0, angle));
+    #[allow(clippy::cast_possible_truncation)]
+    let degrees = (counts * 360).div_euclid(i32::from(ANGLE_MAX)) as i16;
+    degrees
+}
+
+/// Quantize an angle into one of `sectors` equal sectors, computed as
+/// `angle * sectors / 16384`
+///
+/// The result is always in `0..sectors`, or 0 if `sectors` is 0
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::angle::angle_to_sector;
+///
+/// assert_eq!(angle_to_sector(0, 6), 0);
+/// assert_eq!(angle_to_sector(8192, 6), 3);
+/// assert_eq!(angle_to_sector(16383, 6), 5);
+/// assert_eq!(angle_to_sector(16383, 16384), 16383);
+/// assert_eq!(angle_to_sector(100, 0), 0);
+/// ```
+#[must_use]
+pub fn angle_to_sector(angle: u16, sectors: u16) -> u16 {
+    #[allow(clippy::cast_possible_truncation)]
+    let sector = scale(angle, u32::from(sectors)) as u16;
+    sector
+}
+
+/// Map an angle linearly onto a pulse width range in microseconds, rounded
+/// to the nearest microsecond
+///
+/// An angle of 0 maps exactly to `min_us` and the maximum angle 16383 maps
+/// exactly to `max_us`. `max_us` may be less than `min_us` to reverse the
+/// direction
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::angle::angle_to_pulse_us;
+///
+/// assert_eq!(angle_to_pulse_us(0, 1000, 2000), 1000);
+/// assert_eq!(angle_to_pulse_us(8192, 1000, 2000), 1500);
+/// assert_eq!(angle_to_pulse_us(16383, 1000, 2000), 2000);
+/// assert_eq!(angle_to_pulse_us(16383, 2000, 1000), 1000);
+/// assert_eq!(angle_to_pulse_us(16383, 0, u16::MAX), u16::MAX);
+/// ```
+#[must_use]
+pub fn angle_to_pulse_us(angle: u16, min_us: u16, max_us: u16) -> u16 {
+    let full = i64::from(DATA_MASK);
+    let span = i64::from(max_us) - i64::from(min_us);
+    let offset = (2 * i64::from(angle & DATA_MASK) * span + full).div_euclid(2 * full);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let pulse = (i64::from(min_us) + offset) as u16;
+    pulse
+}
+
+/// 14-bit angle in raw sensor counts
+///
+/// Addition and subtraction wrap around within `0..16384`
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::angle::{Degrees, RawAngle};
+///
+/// let angle = RawAngle::new(16000) + RawAngle::new(1000);
+/// assert_eq!(angle.get(), 616);
+/// assert_eq!(RawAngle::new(8192).to_degrees(), Degrees::new(180));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "u16", into = "u16"))]
+pub struct RawAngle(u16);
+
+impl RawAngle {
+    /// Create a raw angle, discarding bits above the 14-bit range
+    #[must_use]
+    pub const fn new(raw: u16) -> Self {
+        Self(raw & DATA_MASK)
+    }
+
+    /// Raw 14-bit value
+    #[must_use]
+    pub const fn get(self) -> u16 {
+        self.0
+    }
+
+    /// Check whether the angle lies on the arc from `start` to `end` in the
+    /// direction of increasing counts, inclusive of both ends
+    ///
+    /// The arc may wrap around the 0/16383 boundary. Ordering with `<` only
+    /// compares raw counts and is not meaningful on a circle
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use as5047d::angle::RawAngle;
+    ///
+    /// let (start, end) = (RawAngle::new(1000), RawAngle::new(2000));
+    /// assert!(RawAngle::new(1500).is_between(start, end));
+    /// assert!(!RawAngle::new(3000).is_between(start, end));
+    ///
+    /// // Arc straddling 0
+    /// let (start, end) = (RawAngle::new(16000), RawAngle::new(300));
+    /// assert!(RawAngle::new(16383).is_between(start, end));
+    /// assert!(RawAngle::new(0).is_between(start, end));
+    /// assert!(RawAngle::new(300).is_between(start, end));
+    /// assert!(!RawAngle::new(301).is_between(start, end));
+    /// assert!(!RawAngle::new(15999).is_between(start, end));
+    /// ```
+    #[must_use]
+    pub fn is_between(self, start: RawAngle, end: RawAngle) -> bool {
+        (self - start).0 <= (end - start).0
+    }
+
+    /// Convert to whole degrees (0-359), rounded to the nearest degree
+    ///
+    /// Angles above 359.5° wrap to 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use as5047d::angle::RawAngle;
+    ///
+    /// // 0.483° and 0.505°
+    /// assert_eq!(RawAngle::new(22).to_degrees().get(), 0);
+    /// assert_eq!(RawAngle::new(23).to_degrees().get(), 1);
+    /// // 0.989°
+    /// assert_eq!(RawAngle::new(45).to_degrees().get(), 1);
+    /// // 359.495° and 359.978°, the maximum raw value
+    /// assert_eq!(RawAngle::new(16361).to_degrees().get(), 359);
+    /// assert_eq!(RawAngle::new(0x3FFF).to_degrees().get(), 0);
+    /// ```
+    #[must_use]
+    pub fn to_degrees(self) -> Degrees {
+        Degrees(utils::to_degrees(self.0))
+    }
+
+    /// Convert to radians, in the range `[0, 2π)`
+    #[cfg(feature = "float")]
+    #[must_use]
+    pub fn to_radians(self) -> f32 {
+        f32::from(self.0) / f32::from(ANGLE_MAX) * core::f32::consts::TAU
+    }
+}
+
+impl From<u16> for RawAngle {
+    fn from(raw: u16) -> Self {
+        Self::new(raw)
+    }
+}
+
+impl From<RawAngle> for u16 {
+    fn from(angle: RawAngle) -> Self {
+        angle.0
+    }
+}
+
+impl Add for RawAngle {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.0.wrapping_add(rhs.0))
+    }
+}
+
+impl Sub for RawAngle {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+/// Angle in whole degrees
+///
+/// Addition and subtraction wrap around within `0..360`
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::angle::Degrees;
+///
+/// assert_eq!(Degrees::new(350) + Degrees::new(20), Degrees::new(10));
+/// assert_eq!(Degrees::new(10) - Degrees::new(20), Degrees::new(350));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "u16", into = "u16"))]
+pub struct Degrees(u16);
+
+impl Degrees {
+    /// Create an angle in degrees, wrapping it into `0..360`
+    #[must_use]
+    pub const fn new(degrees: u16) -> Self {
+        Self(degrees % 360)
+    }
+
+    /// Value in degrees (0-359)
+    #[must_use]
+    pub const fn get(self) -> u16 {
+        self.0
+    }
+}
+
+impl From<u16> for Degrees {
+    fn from(degrees: u16) -> Self {
+        Self::new(degrees)
+    }
+}
+
+impl From<Degrees> for u16 {
+    fn from(degrees: Degrees) -> Self {
+        degrees.0
+    }
+}
+
+impl From<RawAngle> for Degrees {
+    fn from(angle: RawAngle) -> Self {
+        angle.to_degrees()
+    }
+}
+
+impl Add for Degrees {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self((self.0 + rhs.0) % 360)
+    }
+}
+
+impl Sub for Degrees {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self((self.0 + 360 - rhs.0) % 360)
+    }
+}
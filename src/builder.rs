@@ -0,0 +1,145 @@
+//! Builder for configuring an AS5047D driver instance
+
+use crate::{driver::As5047d, protocol::DATA_MASK, register::DiagnosticsAgcRegister};
+
+/// Software options applied by the driver
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub(crate) struct Config {
+    pub(crate) invert: bool,
+    pub(crate) offset: u16,
+    pub(crate) offset_mode: OffsetMode,
+    pub(crate) error_clear_policy: ErrorClearPolicy,
+    pub(crate) parity_checking: bool,
+    pub(crate) min_magnitude: u16,
+    pub(crate) agc_min: u8,
+    pub(crate) agc_max: u8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            invert: false,
+            offset: 0,
+            offset_mode: OffsetMode::Wrap,
+            error_clear_policy: ErrorClearPolicy::Never,
+            parity_checking: true,
+            min_magnitude: DiagnosticsAgcRegister::MIN_MAGNITUDE,
+            agc_min: u8::MIN,
+            agc_max: u8::MAX,
+        }
+    }
+}
+
+/// How the software zero offset is subtracted from angle values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OffsetMode {
+    /// Wrap around within the 14-bit angle range, for continuous rotation
+    #[default]
+    Wrap,
+    /// Clamp at 0 instead of wrapping, for limited travel applications
+    Saturate,
+}
+
+/// When the driver clears the error flag after a read or write reports a
+/// sensor error
+///
+/// The sensor error is returned to the caller in every case.
+/// [`As5047d::angle_with_retries`] always clears the error flag before
+/// retrying, independent of this policy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ErrorClearPolicy {
+    /// Clear the error flag after every sensor error
+    Always,
+    /// Clear the error flag after every `n`th sensor error, deferring the
+    /// others to save SPI bandwidth
+    EveryN(u16),
+    /// Never clear the error flag automatically
+    #[default]
+    Never,
+}
+
+/// Builder for an [`As5047d`] driver instance
+///
+/// All options default to the behavior of [`As5047d::new`]
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct As5047dBuilder<SPI> {
+    spi: SPI,
+    config: Config,
+}
+
+impl<SPI> As5047dBuilder<SPI> {
+    /// Create a new builder for the given SPI device
+    pub fn new(spi: SPI) -> Self {
+        Self {
+            spi,
+            config: Config::default(),
+        }
+    }
+
+    /// Create a builder preset for motor feedback
+    ///
+    /// Enables [`Self::auto_clear_errors`] so a transient sensor error does not
+    /// stall the control loop. The rotation direction is not inverted and no
+    /// software offset is applied
+    pub fn for_motor_feedback(spi: SPI) -> Self {
+        Self::new(spi).auto_clear_errors(true)
+    }
+
+    /// Invert the rotation direction reported by the angle methods
+    #[must_use]
+    pub fn invert_direction(mut self, invert: bool) -> Self {
+        self.config.invert = invert;
+        self
+    }
+
+    /// Set a 14-bit zero offset that the driver subtracts from angle values
+    /// before returning them
+    ///
+    /// The offset is applied in software and does not touch the zero position
+    /// registers
+    #[must_use]
+    pub fn software_offset(mut self, offset: u16) -> Self {
+        self.config.offset = offset & DATA_MASK;
+        self
+    }
+
+    /// Set how the software offset is subtracted from angle values
+    ///
+    /// Defaults to [`OffsetMode::Wrap`]
+    #[must_use]
+    pub fn offset_mode(mut self, mode: OffsetMode) -> Self {
+        self.config.offset_mode = mode;
+        self
+    }
+
+    /// Automatically clear the error flag when a read reports a sensor error
+    ///
+    /// The sensor error is still returned to the caller
+    #[must_use]
+    pub fn auto_clear_errors(mut self, auto_clear_errors: bool) -> Self {
+        self.config.error_clear_policy = if auto_clear_errors {
+            ErrorClearPolicy::Always
+        } else {
+            ErrorClearPolicy::Never
+        };
+        self
+    }
+
+    /// Set when the error flag is cleared after a read reports a sensor error
+    ///
+    /// Defaults to [`ErrorClearPolicy::Never`]
+    #[must_use]
+    pub fn error_clear_policy(mut self, policy: ErrorClearPolicy) -> Self {
+        self.config.error_clear_policy = policy;
+        self
+    }
+
+    /// Build the configured driver instance
+    pub fn build(self) -> As5047d<SPI> {
+        As5047d::with_config(self.spi, self.config)
+    }
+}
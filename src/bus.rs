@@ -0,0 +1,172 @@
+//! SPI device adapters that enforce the chip select timing requirements
+
+use embedded_hal::{
+    delay::DelayNs,
+    digital::OutputPin,
+    spi::{self, ErrorKind, ErrorType, Operation, SpiBus, SpiDevice},
+};
+
+use crate::{driver::As5047d, protocol::MIN_CS_HIGH_NS};
+
+/// AS5047D driver instance using a raw SPI bus and chip select pin
+pub type As5047dBus<BUS, CS, D> = As5047d<ChipSelectDevice<BUS, CS, D>>;
+
+impl<BUS, CS, D> As5047dBus<BUS, CS, D>
+where
+    BUS: SpiBus<u8>,
+    CS: OutputPin,
+    D: DelayNs,
+{
+    /// Create a new AS5047D driver instance from a raw SPI bus
+    ///
+    /// The driver asserts `cs` around each frame and uses `delay` to keep it
+    /// high for at least 350ns between frames
+    pub fn from_bus(bus: BUS, cs: CS, delay: D) -> Self {
+        Self::new(ChipSelectDevice::new(bus, cs, delay))
+    }
+}
+
+impl<SPI, D> As5047d<DelayedDevice<SPI, D>>
+where
+    SPI: SpiDevice<u8>,
+    D: DelayNs,
+{
+    /// Create a new AS5047D driver instance that waits at least 350ns after
+    /// each SPI transaction
+    ///
+    /// Use this when the SPI device does not already guarantee the minimum
+    /// chip select high time between frames
+    pub fn with_delay(spi: SPI, delay: D) -> Self {
+        Self::new(DelayedDevice::new(spi, delay))
+    }
+}
+
+/// Error type for [`ChipSelectDevice`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChipSelectError<BUS, CS> {
+    /// Error from the SPI bus
+    Spi(BUS),
+    /// Error from the chip select pin
+    ChipSelect(CS),
+}
+
+impl<BUS, CS> spi::Error for ChipSelectError<BUS, CS>
+where
+    BUS: spi::Error,
+    CS: core::fmt::Debug,
+{
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Spi(e) => e.kind(),
+            Self::ChipSelect(_) => ErrorKind::ChipSelectFault,
+        }
+    }
+}
+
+/// SPI device that drives chip select itself and enforces the minimum
+/// chip select high time after each transaction
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChipSelectDevice<BUS, CS, D> {
+    bus: BUS,
+    cs: CS,
+    delay: D,
+}
+
+impl<BUS, CS, D> ChipSelectDevice<BUS, CS, D> {
+    /// Create a new chip select device
+    pub fn new(bus: BUS, cs: CS, delay: D) -> Self {
+        Self { bus, cs, delay }
+    }
+
+    /// Release the SPI bus, chip select pin, and delay
+    pub fn release(self) -> (BUS, CS, D) {
+        (self.bus, self.cs, self.delay)
+    }
+}
+
+impl<BUS, CS, D> ErrorType for ChipSelectDevice<BUS, CS, D>
+where
+    BUS: SpiBus<u8>,
+    CS: OutputPin,
+{
+    type Error = ChipSelectError<BUS::Error, CS::Error>;
+}
+
+impl<BUS, CS, D> SpiDevice<u8> for ChipSelectDevice<BUS, CS, D>
+where
+    BUS: SpiBus<u8>,
+    CS: OutputPin,
+    D: DelayNs,
+{
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        self.cs.set_low().map_err(ChipSelectError::ChipSelect)?;
+
+        let result = operations
+            .iter_mut()
+            .try_for_each(|operation| match operation {
+                Operation::Read(read) => self.bus.read(read),
+                Operation::Write(write) => self.bus.write(write),
+                Operation::Transfer(read, write) => self.bus.transfer(read, write),
+                Operation::TransferInPlace(words) => self.bus.transfer_in_place(words),
+                Operation::DelayNs(ns) => {
+                    self.bus.flush()?;
+                    self.delay.delay_ns(*ns);
+                    Ok(())
+                }
+            });
+
+        let flush = self.bus.flush();
+        let cs = self.cs.set_high();
+
+        result.and(flush).map_err(ChipSelectError::Spi)?;
+        cs.map_err(ChipSelectError::ChipSelect)?;
+
+        self.delay.delay_ns(MIN_CS_HIGH_NS);
+
+        Ok(())
+    }
+}
+
+/// SPI device that enforces the minimum chip select high time after each
+/// transaction of an existing SPI device
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DelayedDevice<SPI, D> {
+    spi: SPI,
+    delay: D,
+}
+
+impl<SPI, D> DelayedDevice<SPI, D> {
+    /// Create a new delayed device
+    pub fn new(spi: SPI, delay: D) -> Self {
+        Self { spi, delay }
+    }
+
+    /// Release the SPI device and delay
+    pub fn release(self) -> (SPI, D) {
+        (self.spi, self.delay)
+    }
+}
+
+impl<SPI, D> ErrorType for DelayedDevice<SPI, D>
+where
+    SPI: SpiDevice<u8>,
+{
+    type Error = SPI::Error;
+}
+
+impl<SPI, D> SpiDevice<u8> for DelayedDevice<SPI, D>
+where
+    SPI: SpiDevice<u8>,
+    D: DelayNs,
+{
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        let result = self.spi.transaction(operations);
+
+        self.delay.delay_ns(MIN_CS_HIGH_NS);
+
+        result
+    }
+}
@@ -0,0 +1,61 @@
+//! Wrap-aware change detection for turning angle readings into events
+
+use crate::{angle, protocol::DATA_MASK};
+
+/// Reports an angle reading only once it has moved more than a threshold
+/// away from the last reported reading
+///
+/// Movement is measured along the shortest arc, so readings on either side
+/// of the 0/16383 boundary are close together
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::ChangeDetector;
+///
+/// let mut detector = ChangeDetector::new(10);
+/// assert_eq!(detector.changed(16380), Some(16380));
+/// assert_eq!(detector.changed(5), None);
+/// assert_eq!(detector.changed(10), Some(10));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChangeDetector {
+    threshold: u16,
+    last: Option<u16>,
+}
+
+impl ChangeDetector {
+    /// Create a new detector with a threshold in counts
+    #[must_use]
+    pub const fn new(threshold_counts: u16) -> Self {
+        Self {
+            threshold: threshold_counts,
+            last: None,
+        }
+    }
+
+    /// Update the detector with an angle reading
+    ///
+    /// Returns `Some` with the reading if it is the first one or it moved more
+    /// than the threshold since the last reported reading, which becomes the
+    /// new baseline
+    pub fn changed(&mut self, raw: u16) -> Option<u16> {
+        let raw = raw & DATA_MASK;
+
+        if let Some(last) = self.last
+            && angle::shortest_delta(last, raw).unsigned_abs() <= self.threshold
+        {
+            return None;
+        }
+
+        self.last = Some(raw);
+        Some(raw)
+    }
+
+    /// Last reported reading, if any
+    #[must_use]
+    pub const fn last(&self) -> Option<u16> {
+        self.last
+    }
+}
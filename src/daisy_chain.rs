@@ -0,0 +1,65 @@
+//! Multiple AS5047D sensors daisy-chained on a single chip select
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::{error::Error, parity::EvenParity, protocol::NOP_COMMAND, register::Register, utils};
+
+/// Driver for `N` AS5047D sensors connected in a daisy chain
+///
+/// In a daisy chain every transaction must clock exactly `N * 16` bits while
+/// chip select is low, so that each sensor receives its own 16-bit frame. As
+/// with a single sensor, the data for a command is returned in the following
+/// transaction
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DaisyChain<SPI, const N: usize> {
+    spi: SPI,
+}
+
+impl<SPI, E, const N: usize> DaisyChain<SPI, N>
+where
+    SPI: SpiDevice<u8, Error = E>,
+{
+    /// Create a new daisy chain driver instance
+    pub fn new(spi: SPI) -> Self {
+        Self { spi }
+    }
+
+    /// Release the SPI bus, consuming the driver
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+
+    /// Get the 14-bit corrected angular position of every sensor in the chain
+    ///
+    /// The angles are returned in the order they are shifted out of the
+    /// chain, so the first entry belongs to the sensor whose MISO is connected
+    /// to the controller
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, or parity check fails or
+    /// the sensor reports an error for any sensor in the chain
+    pub fn angles(&mut self) -> Result<[u16; N], Error<E>> {
+        self.transfer([utils::read_command::<EvenParity>(Register::AngleCom); N])?;
+        let responses = self.transfer([NOP_COMMAND; N])?;
+
+        let mut angles = [0u16; N];
+        for (angle, response) in angles.iter_mut().zip(responses) {
+            *angle = utils::parse_response::<EvenParity, _>(response)?;
+        }
+
+        Ok(angles)
+    }
+
+    /// Transfer one frame to each sensor in a single transaction
+    fn transfer(&mut self, frames: [u16; N]) -> Result<[u16; N], Error<E>> {
+        let tx = frames.map(u16::to_be_bytes);
+        let mut rx = [[0u8; 2]; N];
+        self.spi
+            .transfer(rx.as_flattened_mut(), tx.as_flattened())
+            .map_err(Error::Communication)?;
+
+        Ok(rx.map(u16::from_be_bytes))
+    }
+}
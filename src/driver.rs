@@ -1,41 +1,219 @@
-//! Asynchronous driver for AS5047D magnetic position sensor
+//! Blocking driver for AS5047D magnetic position sensor
 
-use embedded_hal::spi::SpiDevice;
+use core::marker::PhantomData;
+
+use embedded_hal::{delay::DelayNs, spi::SpiDevice};
 
 use crate::{
+    angle::{self, RawAngle},
+    builder::{As5047dBuilder, Config, ErrorClearPolicy, OffsetMode},
     error::Error,
+    parity::{EvenParity, ParityScheme},
+    protocol::{DATA_MASK, ERROR_FLAG, NOP_COMMAND, PARITY_BIT, READ_BIT},
     register::{
         DiagnosticsAgcRegister, ErrorFlagRegister, Register, ZeroPositionLsbRegister,
         ZeroPositionMsbRegister,
     },
+    snapshot::{FieldReport, SensorSnapshot},
+    transfer::FrameTransfer,
     utils,
 };
 
-const READ_BIT: u16 = 0x4000;
-const PARITY_BIT: u16 = 0x8000;
-const ERROR_FLAG: u16 = 0x4000;
-const DATA_MASK: u16 = 0x3FFF;
-const NOP_COMMAND: u16 = 0x0000;
+#[cfg(feature = "otp-programming")]
+use crate::register::ProgrammingRegister;
 
 /// Maximum angle value (14-bit: 0-16383, representing 0-360°)
 pub const ANGLE_MAX: u16 = 0x3FFF + 1;
 
-/// AS5047D driver instance (asynchronous)
+/// AS5047D driver instance (blocking)
+///
+/// The parity scheme `P` only needs to be changed for fault injection
+/// testing, see [`ParityScheme`]
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct As5047d<SPI> {
+pub struct As5047d<SPI, P = EvenParity> {
     spi: SPI,
+    config: Config,
+    /// Whether the last frame sent was an angle read command
+    primed: bool,
+    /// Whether the error flag was set in the last response read
+    last_error_flag: bool,
+    /// Last successfully read corrected angle
+    last_angle: Option<u16>,
+    /// Sensor errors not yet cleared under [`ErrorClearPolicy::EveryN`]
+    deferred_errors: u16,
+    parity: PhantomData<P>,
+}
+
+/// Cloning creates another handle to the same sensor, for example with a
+/// shared-bus SPI device. Each handle keeps its own configuration and cached
+/// state. The sensor's pipelined responses assume that no other handle sends
+/// frames in between, so methods such as [`As5047d::angle_pipelined`] must
+/// not be interleaved across handles
+impl<SPI: Clone, P> Clone for As5047d<SPI, P> {
+    fn clone(&self) -> Self {
+        Self {
+            spi: self.spi.clone(),
+            config: self.config,
+            primed: false,
+            last_error_flag: self.last_error_flag,
+            last_angle: self.last_angle,
+            deferred_errors: self.deferred_errors,
+            parity: PhantomData,
+        }
+    }
+}
+
+impl<SPI> As5047d<SPI> {
+    /// Create a builder for configuring a driver instance
+    pub fn builder(spi: SPI) -> As5047dBuilder<SPI> {
+        As5047dBuilder::new(spi)
+    }
+
+    pub(crate) fn with_config(spi: SPI, config: Config) -> Self {
+        Self {
+            spi,
+            config,
+            primed: false,
+            last_error_flag: false,
+            last_angle: None,
+            deferred_errors: 0,
+            parity: PhantomData,
+        }
+    }
+}
+
+impl<SPI, P> As5047d<SPI, P> {
+    /// Use a different parity scheme for command and response frames
+    ///
+    /// Only intended for fault injection testing, see [`ParityScheme`]
+    pub fn with_parity_scheme<Q: ParityScheme>(self) -> As5047d<SPI, Q> {
+        As5047d {
+            spi: self.spi,
+            config: self.config,
+            primed: false,
+            last_error_flag: self.last_error_flag,
+            last_angle: self.last_angle,
+            deferred_errors: self.deferred_errors,
+            parity: PhantomData,
+        }
+    }
+
+    /// Run a closure with temporary mutable access to the SPI device
+    ///
+    /// Useful for interleaving other traffic on a shared bus without
+    /// releasing the driver. The closure must not leave the sensor in the
+    /// middle of a command sequence, such as after a write command frame
+    /// without its data frame
+    pub fn with_spi<R>(&mut self, f: impl FnOnce(&mut SPI) -> R) -> R {
+        self.primed = false;
+        f(&mut self.spi)
+    }
+
+    /// Invert the rotation direction reported by the angle methods
+    ///
+    /// The inversion is applied after the response is validated and before
+    /// any unit conversion
+    pub fn set_invert(&mut self, invert: bool) {
+        self.config.invert = invert;
+    }
+
+    /// Enable or disable parity checking of response frames
+    ///
+    /// Parity checking is enabled by default
+    ///
+    /// **Warning:** with parity checking disabled, corrupted frames are
+    /// returned as if they were valid. This is intended only for diagnosing
+    /// wiring problems and must not be used in production
+    pub fn set_parity_checking(&mut self, enabled: bool) {
+        self.config.parity_checking = enabled;
+    }
+
+    /// Whether the error flag was set in the most recent response, including
+    /// the verification frame of a write and the frames returned by
+    /// [`Self::transfer_raw`] and [`Self::read_frame`]. A response that fails
+    /// the parity check does not change it
+    ///
+    /// This is cached from the last read and does not communicate with the
+    /// sensor, so clearing the error flag can be deferred and batched
+    #[must_use]
+    pub fn last_error_flag(&self) -> bool {
+        self.last_error_flag
+    }
+
+    /// Set when the error flag is cleared after a read reports a sensor error
+    ///
+    /// Defaults to [`ErrorClearPolicy::Never`]
+    pub fn set_error_clear_policy(&mut self, policy: ErrorClearPolicy) {
+        self.config.error_clear_policy = policy;
+        self.deferred_errors = 0;
+    }
+
+    /// Set the magnet field thresholds used by the validity helpers
+    ///
+    /// [`Self::is_magnet_present`] requires a magnitude of at least
+    /// `min_magnitude`, and [`Self::angle_if_valid`] additionally requires
+    /// the AGC value to be within `agc_min..=agc_max`. The defaults are
+    /// [`DiagnosticsAgcRegister::MIN_MAGNITUDE`] and the full AGC range, since
+    /// the datasheet only flags field strength through MAGH and MAGL
+    ///
+    /// The AGC bounds are swapped if `agc_min` is greater than `agc_max`
+    pub fn set_field_thresholds(&mut self, min_magnitude: u16, agc_min: u8, agc_max: u8) {
+        self.config.min_magnitude = min_magnitude;
+        self.config.agc_min = agc_min.min(agc_max);
+        self.config.agc_max = agc_min.max(agc_max);
+    }
+
+    /// Last successfully read corrected angle
+    ///
+    /// Returns `None` before the first successful angle read. Failed reads do
+    /// not change the value, so it can be used as a fallback after a
+    /// transient error
+    #[must_use]
+    pub fn last_angle(&self) -> Option<u16> {
+        self.last_angle
+    }
+
+    /// Set a 14-bit zero offset that is subtracted from angle values
+    ///
+    /// The subtraction wraps around within the 14-bit angle range unless
+    /// [`OffsetMode::Saturate`] is selected. The offset is only stored in
+    /// the driver and does not touch the zero position registers
+    pub fn set_software_zero(&mut self, offset: u16) {
+        self.config.offset = offset & DATA_MASK;
+    }
+
+    /// Set how the software zero offset is subtracted from angle values
+    ///
+    /// Defaults to [`OffsetMode::Wrap`]
+    pub fn set_offset_mode(&mut self, mode: OffsetMode) {
+        self.config.offset_mode = mode;
+    }
 }
 
 impl<SPI, E> As5047d<SPI>
 where
-    SPI: SpiDevice<u8, Error = E>,
+    SPI: FrameTransfer<Error = E>,
 {
     /// Create a new AS5047D driver instance
     pub fn new(spi: SPI) -> Self {
-        Self { spi }
+        Self::with_config(spi, Config::default())
+    }
+
+    /// Create a new AS5047D driver instance with the rotation direction
+    /// inverted
+    ///
+    /// Equivalent to `As5047d::builder(spi).invert_direction(true).build()`
+    pub fn new_inverted(spi: SPI) -> Self {
+        Self::builder(spi).invert_direction(true).build()
     }
+}
 
+impl<SPI, E, P> As5047d<SPI, P>
+where
+    SPI: FrameTransfer<Error = E>,
+    P: ParityScheme,
+{
     /// Release the SPI bus, consuming the driver
     pub fn release(self) -> SPI {
         self.spi
@@ -47,15 +225,51 @@ where
     /// - Transaction 1: Send read command, ignore response
     /// - Transaction 2: Send NOP, receive actual data
     fn read_register(&mut self, register: Register) -> Result<u16, Error<E>> {
-        let address = u16::from(register);
+        self.transfer(utils::read_command::<P>(register))?;
+        let response = self.transfer(NOP_COMMAND)?;
+        let data = self.parse_response(response)?;
 
-        let command = READ_BIT | address;
+        #[cfg(feature = "defmt")]
+        defmt::debug!(
+            "Register 0x{:04X} value: 0x{:04X}",
+            u16::from(register),
+            data
+        );
 
-        let command = if utils::calculate_parity(command) {
-            PARITY_BIT | command
-        } else {
-            command
-        };
+        Ok(data)
+    }
+
+    /// Count a sensor error and decide whether to clear the error flag now,
+    /// according to the error clear policy
+    fn should_clear_error(&mut self) -> bool {
+        match self.config.error_clear_policy {
+            ErrorClearPolicy::Always => true,
+            ErrorClearPolicy::Never => false,
+            ErrorClearPolicy::EveryN(n) => {
+                self.deferred_errors = self.deferred_errors.saturating_add(1);
+                if self.deferred_errors < n {
+                    return false;
+                }
+
+                self.deferred_errors = 0;
+                true
+            }
+        }
+    }
+
+    /// Read a register from the AS5047D without checking the error flag
+    ///
+    /// Returns the 14-bit data together with the state of the error flag, so
+    /// the caller can decide how to recover from a sensor error. The response
+    /// parity is still verified
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails or parity check fails
+    pub fn read_register_raw(&mut self, register: Register) -> Result<(u16, bool), Error<E>> {
+        let address = u16::from(register);
+
+        let command = utils::with_parity::<P>(READ_BIT | address);
 
         #[cfg(feature = "defmt")]
         defmt::trace!(
@@ -64,44 +278,68 @@ where
             command
         );
 
-        let tx_cmd = command.to_be_bytes();
-        let mut rx_cmd = [0u8; 2];
-        self.spi
-            .transfer(&mut rx_cmd, &tx_cmd)
-            .map_err(Error::Communication)?;
-
-        let tx_nop = NOP_COMMAND.to_be_bytes();
-        let mut rx_data = [0u8; 2];
-        self.spi
-            .transfer(&mut rx_data, &tx_nop)
-            .map_err(Error::Communication)?;
-
-        let response = u16::from_be_bytes(rx_data);
+        self.transfer(command)?;
+        let response = self.transfer(NOP_COMMAND)?;
 
         #[cfg(feature = "defmt")]
         defmt::trace!("Received response: 0x{:04X}", response);
 
-        if !utils::verify_parity(response) {
-            #[cfg(feature = "defmt")]
-            defmt::warn!("Parity error in response: 0x{:04X}", response);
-            return Err(Error::ParityError);
-        }
+        let error_flag = self.check_frame(response)?;
+
+        Ok((response & DATA_MASK, error_flag))
+    }
 
-        if response & ERROR_FLAG != 0 {
+    /// Check a response frame and extract its data
+    ///
+    /// Every response the driver parses goes through here, so the error
+    /// clear policy applies to single reads, pipelined reads and writes alike
+    fn parse_response(&mut self, response: u16) -> Result<u16, Error<E>> {
+        if self.check_frame(response)? {
             #[cfg(feature = "defmt")]
             defmt::warn!("Sensor error flag set in response");
+
+            if self.should_clear_error() {
+                self.clear_error_flag()?;
+            }
+
             return Err(Error::SensorError);
         }
 
-        let data = response & DATA_MASK;
-        #[cfg(feature = "defmt")]
-        defmt::debug!("Register 0x{:04X} value: 0x{:04X}", address, data);
+        Ok(response & DATA_MASK)
+    }
 
-        Ok(data)
+    /// Verify the parity of a response frame and record its error flag
+    fn check_frame(&mut self, response: u16) -> Result<bool, Error<E>> {
+        self.verify_parity(response)?;
+
+        self.last_error_flag = response & ERROR_FLAG != 0;
+
+        Ok(self.last_error_flag)
+    }
+
+    /// Verify the parity of a response frame, honoring the parity checking
+    /// setting
+    fn verify_parity(&self, response: u16) -> Result<(), Error<E>> {
+        if self.config.parity_checking && !P::verify(response) {
+            #[cfg(feature = "defmt")]
+            defmt::warn!("Parity error in response: 0x{:04X}", response);
+            return Err(Error::ParityError);
+        }
+
+        Ok(())
+    }
+
+    /// Transfer a single 16-bit frame and return the received frame
+    fn transfer(&mut self, frame: u16) -> Result<u16, Error<E>> {
+        let result = self.spi.transfer_frame(frame).map_err(Error::Communication);
+        self.primed = result.is_ok() && frame == utils::read_command::<P>(Register::AngleCom);
+        result
     }
 
     /// Write a register to the AS5047D
     ///
+    /// Only the lower 14 bits of `data` are written.
+    ///
     /// This follows the write protocol:
     /// - Transaction 1: Send write command
     /// - Transaction 2: Send data frame
@@ -113,64 +351,87 @@ where
     /// - SPI communication fails
     /// - Parity check fails on the response
     /// - The sensor reports an error
-    #[allow(dead_code)]
-    fn write_register(&mut self, register: Register, data: u16) -> Result<(), Error<E>> {
+    pub fn write_register(&mut self, register: Register, data: u16) -> Result<(), Error<E>> {
         let address = u16::from(register);
 
         #[cfg(feature = "defmt")]
         defmt::debug!("Writing 0x{:04X} to register 0x{:04X}", data, address);
 
-        let command = address;
+        let command = utils::with_parity::<P>(address);
 
-        let command = if utils::calculate_parity(command) {
-            PARITY_BIT | command
-        } else {
-            command
-        };
+        self.transfer(command)?;
 
-        let tx_cmd = command.to_be_bytes();
-        let mut rx_cmd = [0u8; 2];
-        self.spi
-            .transfer(&mut rx_cmd, &tx_cmd)
-            .map_err(Error::Communication)?;
+        let data_frame = utils::with_parity::<P>(data & DATA_MASK);
+        self.transfer(data_frame)?;
 
-        let data_frame = data & DATA_MASK;
-        let data_frame = if utils::calculate_parity(data_frame) {
-            PARITY_BIT | data_frame
-        } else {
-            data_frame
-        };
+        let response = self.transfer(NOP_COMMAND)?;
+        self.parse_response(response)?;
 
-        let tx_data = data_frame.to_be_bytes();
-        let mut rx_old = [0u8; 2];
-        self.spi
-            .transfer(&mut rx_old, &tx_data)
-            .map_err(Error::Communication)?;
+        #[cfg(feature = "defmt")]
+        defmt::trace!("Write to register 0x{:04X} successful", address);
 
-        let tx_nop = NOP_COMMAND.to_be_bytes();
-        let mut rx_verify = [0u8; 2];
-        self.spi
-            .transfer(&mut rx_verify, &tx_nop)
-            .map_err(Error::Communication)?;
+        Ok(())
+    }
 
-        let response = u16::from_be_bytes(rx_verify);
+    /// Write a register and read it back to confirm the write
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::VerifyFailed`] if the value read back does not match
+    /// the lower 14 bits of `data`, or an error if SPI communication fails,
+    /// parity check fails, or the sensor reports an error
+    pub fn write_register_verified(
+        &mut self,
+        register: Register,
+        data: u16,
+    ) -> Result<(), Error<E>> {
+        self.write_register(register, data)?;
 
-        if !utils::verify_parity(response) {
-            #[cfg(feature = "defmt")]
-            defmt::warn!("Parity error in write verification: 0x{:04X}", response);
-            return Err(Error::ParityError);
-        }
+        let expected = data & DATA_MASK;
+        let actual = self.read_register(register)?;
 
-        if response & ERROR_FLAG != 0 {
-            #[cfg(feature = "defmt")]
-            defmt::warn!("Sensor error flag set during write");
-            return Err(Error::SensorError);
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(Error::VerifyFailed { expected, actual })
         }
+    }
 
-        #[cfg(feature = "defmt")]
-        defmt::trace!("Write to register 0x{:04X} successful", address);
+    /// Send a raw command frame followed by a NOP and return the raw response
+    ///
+    /// The parity bit of `command` is set by the driver and the parity of the
+    /// response is verified unless disabled with
+    /// [`Self::set_parity_checking`]. The error flag is not checked and the
+    /// data is not masked, so interpreting the response is the caller's
+    /// responsibility
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails or parity check fails
+    pub fn transfer_raw(&mut self, command: u16) -> Result<u16, Error<E>> {
+        self.transfer(utils::with_parity::<P>(command & !PARITY_BIT))?;
+        let response = self.transfer(NOP_COMMAND)?;
+        self.check_frame(response)?;
 
-        Ok(())
+        Ok(response)
+    }
+
+    /// Read a register and return the complete 16-bit response frame
+    ///
+    /// Unlike [`Self::read_register_raw`], the response is returned exactly as
+    /// received, including the parity bit and error flag, and its parity is
+    /// not verified. The error flag is still recorded for
+    /// [`Self::last_error_flag`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails
+    pub fn read_frame(&mut self, register: Register) -> Result<u16, Error<E>> {
+        self.transfer(utils::read_command::<P>(register))?;
+        let response = self.transfer(NOP_COMMAND)?;
+        self.last_error_flag = response & ERROR_FLAG != 0;
+
+        Ok(response)
     }
 
     fn modify_register<R>(
@@ -187,6 +448,59 @@ where
         Ok(result)
     }
 
+    /// Check communication with the sensor by reading the NOP register
+    ///
+    /// A successful ping confirms that the SPI link works and that the
+    /// response parity is valid. The returned data is not checked
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn ping(&mut self) -> Result<(), Error<E>> {
+        self.read_register(Register::Nop).map(|_| ())
+    }
+
+    /// Check for gross SPI misconfiguration, such as the wrong SPI mode
+    ///
+    /// Reads the NOP register several times and checks that every response
+    /// frame is exactly `0x0000`, as sent by a correctly connected sensor.
+    /// With the wrong clock polarity or phase, the response bits are shifted
+    /// or garbled and usually fail this check
+    ///
+    /// This cannot detect every problem: in particular, a MISO line stuck low
+    /// also reads as `0x0000`. Use [`Self::diagnostics`] afterwards to check
+    /// that the sensor responds with meaningful data
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ParityError`] if a response frame fails the parity
+    /// check, [`Error::SensorError`] if it has the error flag set,
+    /// [`Error::UnexpectedResponse`] if it is otherwise not `0x0000`, or an
+    /// error if SPI communication fails
+    pub fn self_test(&mut self) -> Result<(), Error<E>> {
+        const READS: usize = 4;
+
+        for _ in 0..READS {
+            let frame = self.read_frame(Register::Nop)?;
+            if frame == 0 {
+                continue;
+            }
+
+            #[cfg(feature = "defmt")]
+            defmt::warn!("Self test read unexpected frame 0x{:04X}", frame);
+
+            self.verify_parity(frame)?;
+
+            return Err(if self.last_error_flag {
+                Error::SensorError
+            } else {
+                Error::UnexpectedResponse(frame)
+            });
+        }
+
+        Ok(())
+    }
+
     /// Get the 14-bit corrected angular position
     ///
     /// Value ranges from 0 to 16383 (0° to 359.978°)
@@ -194,26 +508,478 @@ where
     ///
     /// For integer degree conversion, use [`Self::angle_degrees`]
     ///
+    /// The software direction inversion and zero offset configured through
+    /// [`As5047dBuilder`] are applied to the returned value
+    ///
     /// # Errors
     ///
     /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
     pub fn angle(&mut self) -> Result<u16, Error<E>> {
         self.read_register(Register::AngleCom)
+            .map(|angle| self.apply_config(angle))
+    }
+
+    /// Get the 14-bit corrected angular position as a [`RawAngle`]
+    ///
+    /// Same as [`Self::angle`], but typed to avoid mixing up raw counts and
+    /// degrees
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn angle_typed(&mut self) -> Result<RawAngle, Error<E>> {
+        self.angle().map(RawAngle::new)
+    }
+
+    /// Get the 14-bit angular position without dynamic angle error
+    /// compensation
+    ///
+    /// Reads the ANGLEUNC register. The software direction inversion and zero
+    /// offset are applied as for [`Self::angle`], so the two values can be
+    /// compared directly. The result is not recorded as the last angle
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn angle_uncompensated(&mut self) -> Result<u16, Error<E>> {
+        self.read_register(Register::AngleUnc)
+            .map(|angle| self.transform(angle))
+    }
+
+    /// Apply the software direction inversion and zero offset to a raw angle
+    ///
+    /// The result is recorded as the last successfully read angle
+    fn apply_config(&mut self, angle: u16) -> u16 {
+        let angle = self.transform(angle);
+        self.last_angle = Some(angle);
+        angle
+    }
+
+    /// Apply the software direction inversion and zero offset to a raw angle
+    /// without recording it
+    fn transform(&self, angle: u16) -> u16 {
+        let angle = if self.config.invert {
+            ANGLE_MAX.wrapping_sub(angle) & DATA_MASK
+        } else {
+            angle
+        };
+
+        match self.config.offset_mode {
+            OffsetMode::Wrap => angle.wrapping_sub(self.config.offset) & DATA_MASK,
+            OffsetMode::Saturate => angle.saturating_sub(self.config.offset),
+        }
+    }
+
+    /// Get the 14-bit corrected angular position, retrying on transient errors
+    ///
+    /// The angle is read up to `attempts` times (at least once). A parity
+    /// error is retried directly, and a sensor error clears the error flag
+    /// before retrying. The last error is returned if all attempts fail
+    ///
+    /// Communication errors are not retried, since they usually indicate a
+    /// configuration problem rather than a transient fault
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, or if every attempt fails
+    /// the parity check or reports a sensor error
+    pub fn angle_with_retries(&mut self, attempts: u8) -> Result<u16, Error<E>> {
+        let mut remaining = attempts.max(1);
+
+        loop {
+            remaining -= 1;
+
+            match self.angle() {
+                Err(Error::ParityError) if remaining > 0 => {}
+                Err(Error::SensorError) if remaining > 0 => {
+                    self.clear_error_flag()?;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Prepare the sensor so the next [`Self::angle_pipelined`] call returns
+    /// current data
+    ///
+    /// Each response carries the data of the register addressed by the
+    /// previous frame, so the first pipelined read after startup, or after
+    /// any other command, returns stale data. This sends one throwaway angle
+    /// read command unless the last frame sent already was one
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails
+    pub fn prime(&mut self) -> Result<(), Error<E>> {
+        if !self.primed {
+            self.transfer(utils::read_command::<P>(Register::AngleCom))?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the 14-bit corrected angular position using a single SPI transfer
+    ///
+    /// The AS5047D responds to each command with the data of the register
+    /// addressed by the previous command. This sends the angle read command
+    /// and returns the response to the previous frame, halving the number of
+    /// transfers when reading the angle in a loop
+    ///
+    /// The first call after startup, or after any other frame was sent (for
+    /// example by another method or by an automatic error flag clear), first
+    /// calls [`Self::prime`], so it takes two transfers but never returns
+    /// stale data from the previously addressed register
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn angle_pipelined(&mut self) -> Result<u16, Error<E>> {
+        self.prime()?;
+
+        let command = utils::read_command::<P>(Register::AngleCom);
+
+        let response = self.transfer(command)?;
+
+        self.parse_response(response)
+            .map(|angle| self.apply_config(angle))
+    }
+
+    /// Read `N` consecutive angle samples as fast as possible
+    ///
+    /// The reads are pipelined as in [`Self::angle_pipelined`], after
+    /// calling [`Self::prime`] so that every sample is current. This takes
+    /// at most `N + 1` transfers, so the effective sample rate is bounded by
+    /// the SPI clock and the chip select high time between frames
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered if SPI communication fails, parity
+    /// check fails, or the sensor reports an error
+    pub fn sample_block<const N: usize>(&mut self) -> Result<[u16; N], Error<E>> {
+        let mut samples = [0; N];
+        if N == 0 {
+            return Ok(samples);
+        }
+
+        self.prime()?;
+        for sample in &mut samples {
+            *sample = self.angle_pipelined()?;
+        }
+
+        Ok(samples)
+    }
+
+    /// Get the 14-bit corrected angular position, performing exactly one SPI
+    /// transfer per call
+    ///
+    /// The first call sends the angle read command and returns
+    /// [`nb::Error::WouldBlock`]; the next call sends a NOP and returns the
+    /// angle. The only state held between calls is whether the last frame
+    /// sent was an angle read command, so calling any other method in between
+    /// restarts the read. An error also restarts the read, so no reset is
+    /// needed before polling again
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    #[cfg(feature = "nb")]
+    pub fn poll_angle(&mut self) -> nb::Result<u16, Error<E>> {
+        if !self.primed {
+            self.prime()?;
+            return Err(nb::Error::WouldBlock);
+        }
+
+        let response = self.transfer(NOP_COMMAND)?;
+
+        let angle = self
+            .parse_response(response)
+            .map(|angle| self.apply_config(angle))?;
+        Ok(angle)
+    }
+
+    /// Get the 14-bit corrected angular position together with the
+    /// diagnostics and AGC register
+    ///
+    /// The diagnostics read command is sent in the same transfer that
+    /// returns the angle, so this takes three transfers instead of four
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn angle_with_diagnostics(&mut self) -> Result<(u16, DiagnosticsAgcRegister), Error<E>> {
+        self.transfer(utils::read_command::<P>(Register::AngleCom))?;
+        let angle = self.transfer(utils::read_command::<P>(Register::DiaAgc))?;
+        let diagnostics = self.transfer(NOP_COMMAND)?;
+
+        let angle = self
+            .parse_response(angle)
+            .map(|angle| self.apply_config(angle))?;
+        let diagnostics = self
+            .parse_response(diagnostics)
+            .map(DiagnosticsAgcRegister)?;
+
+        Ok((angle, diagnostics))
+    }
+
+    /// Get the 14-bit corrected angular position, checking that it is valid
+    ///
+    /// The diagnostics register is read together with the angle, see
+    /// [`Self::angle_with_diagnostics`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::DataInvalid`] if a CORDIC overflow occurred or the
+    /// magnetic field is out of range, or an error if SPI communication fails,
+    /// parity check fails, or the sensor reports an error
+    pub fn angle_checked(&mut self) -> Result<u16, Error<E>> {
+        let (angle, diagnostics) = self.angle_with_diagnostics()?;
+
+        if diagnostics.is_valid() {
+            Ok(angle)
+        } else {
+            Err(Error::DataInvalid)
+        }
+    }
+
+    /// Get the 14-bit corrected angular position only if the measurement is
+    /// valid
+    ///
+    /// Returns `Ok(None)` if a CORDIC overflow occurred, the magnetic field
+    /// is out of range, or the AGC value is outside the range set with
+    /// [`Self::set_field_thresholds`], distinguishing a misplaced magnet from
+    /// a communication fault. The angle read command is sent in the same
+    /// transfer that returns the diagnostics, so this takes three transfers,
+    /// or two if the measurement is invalid
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn angle_if_valid(&mut self) -> Result<Option<u16>, Error<E>> {
+        self.transfer(utils::read_command::<P>(Register::DiaAgc))?;
+        let diagnostics = self.transfer(utils::read_command::<P>(Register::AngleCom))?;
+
+        let diagnostics = self
+            .parse_response(diagnostics)
+            .map(DiagnosticsAgcRegister)?;
+        let agc_range = self.config.agc_min..=self.config.agc_max;
+        if !diagnostics.is_valid() || !agc_range.contains(&diagnostics.agc()) {
+            return Ok(None);
+        }
+
+        let angle = self.transfer(NOP_COMMAND)?;
+
+        self.parse_response(angle)
+            .map(|angle| Some(self.apply_config(angle)))
+    }
+
+    /// Read the angle, magnitude, and diagnostics registers together
+    ///
+    /// The reads are pipelined: each read command returns the data of the
+    /// previous command, so the response to the first command is discarded
+    /// and a trailing NOP fetches the diagnostics. This takes four transfers
+    /// instead of six
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn read_all(&mut self) -> Result<SensorSnapshot, Error<E>> {
+        self.transfer(utils::read_command::<P>(Register::AngleCom))?;
+        let angle = self.transfer(utils::read_command::<P>(Register::Mag))?;
+        let magnitude = self.transfer(utils::read_command::<P>(Register::DiaAgc))?;
+        let diagnostics = self.transfer(NOP_COMMAND)?;
+
+        Ok(SensorSnapshot {
+            angle: self
+                .parse_response(angle)
+                .map(|angle| self.apply_config(angle))?,
+            magnitude: self.parse_response(magnitude)?,
+            diagnostics: self
+                .parse_response(diagnostics)
+                .map(DiagnosticsAgcRegister)?,
+        })
+    }
+
+    /// Read the magnitude and the AGC value and field strength flags together
+    ///
+    /// The reads are pipelined, so this takes three transfers instead of four
+    ///
+    /// Together these indicate the air gap between magnet and sensor. With a
+    /// correctly placed magnet, MAGH and MAGL are both clear and the AGC value
+    /// sits between [`DiagnosticsAgcRegister::AGC_TOO_CLOSE`] and
+    /// [`DiagnosticsAgcRegister::AGC_TOO_FAR`], ideally near the middle of its
+    /// 0-255 range. The magnitude should be at least
+    /// [`DiagnosticsAgcRegister::MIN_MAGNITUDE`] (out of the 14-bit range
+    /// 0-16383). It is regulated by the AGC, so it should stay nearly constant
+    /// over a full rotation; a large variation indicates an off-axis magnet
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn field_report(&mut self) -> Result<FieldReport, Error<E>> {
+        self.transfer(utils::read_command::<P>(Register::Mag))?;
+        let magnitude = self.transfer(utils::read_command::<P>(Register::DiaAgc))?;
+        let diagnostics = self.transfer(NOP_COMMAND)?;
+
+        let magnitude = self.parse_response(magnitude)?;
+        let diagnostics = self
+            .parse_response(diagnostics)
+            .map(DiagnosticsAgcRegister)?;
+
+        Ok(FieldReport {
+            magnitude,
+            agc: diagnostics.agc(),
+            magh: diagnostics.magh(),
+            magl: diagnostics.magl(),
+        })
     }
 
     /// Get the angular position in degrees (0-359)
     ///
     /// This method converts the raw 14-bit angle value to degrees using
-    /// integer arithmetic with saturation. The result is rounded down
+    /// integer arithmetic. The result is rounded to the nearest degree, with
+    /// angles above 359.5° wrapping to 0
     ///
     /// # Errors
     ///
     /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
     pub fn angle_degrees(&mut self) -> Result<u16, Error<E>> {
+        self.angle().map(utils::to_degrees)
+    }
+
+    /// Get the angular position in millidegrees (0-359978)
+    ///
+    /// Computed as `raw * 360000 / 16384` and rounded down, giving ~22
+    /// millidegree resolution without floating point. The product overflows
+    /// `u32` for large angles, so a `u64` intermediate is used
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn angle_millidegrees(&mut self) -> Result<u32, Error<E>> {
+        self.angle_scaled(360_000)
+    }
+
+    /// Get the angular position scaled onto the range `0..full_scale`
+    ///
+    /// Useful for mapping the angle onto arbitrary integer ranges without
+    /// floating point. See [`angle::scale`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn angle_scaled(&mut self, full_scale: u32) -> Result<u32, Error<E>> {
+        self.angle().map(|angle| angle::scale(angle, full_scale))
+    }
+
+    /// Get the signed shortest-arc difference in counts from `setpoint` to
+    /// the angular position, in the range `-8192..8192`
+    ///
+    /// See [`angle::shortest_delta`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn error_from(&mut self, setpoint: u16) -> Result<i16, Error<E>> {
+        self.angle()
+            .map(|angle| angle::shortest_delta(setpoint, angle))
+    }
+
+    /// Get the index of the equal sector, out of `sectors`, containing the
+    /// angular position
+    ///
+    /// The result is always in `0..sectors`, see [`angle::angle_to_sector`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn sector(&mut self, sectors: u16) -> Result<u16, Error<E>> {
+        self.angle()
+            .map(|angle| angle::angle_to_sector(angle, sectors))
+    }
+
+    /// Get the angular position in degrees (0-359) relative to a mechanical
+    /// "north" position
+    ///
+    /// `north_offset_raw` is subtracted from the angle returned by
+    /// [`Self::angle`], wrapping around within the 14-bit range, before
+    /// converting to degrees. The north offset is therefore applied on top of
+    /// the configured inversion and software offset. Unlike the zero
+    /// position, this is a read-time transform and does not write to the
+    /// sensor
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn heading_degrees(&mut self, north_offset_raw: u16) -> Result<u16, Error<E>> {
         let angle = self.angle()?;
-        let degrees = (u32::from(angle).saturating_mul(360)) / u32::from(ANGLE_MAX);
-        #[allow(clippy::cast_possible_truncation)]
-        Ok(degrees as u16)
+        Ok(utils::to_degrees(
+            angle.wrapping_sub(north_offset_raw) & DATA_MASK,
+        ))
+    }
+
+    /// Get the angular position in fractional degrees, in the range `[0, 360)`
+    ///
+    /// The raw value is mapped as `raw * 360 / ANGLE_MAX`, giving the full
+    /// ~0.022° resolution of the sensor
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    #[cfg(feature = "float")]
+    pub fn angle_degrees_f32(&mut self) -> Result<f32, Error<E>> {
+        let angle = self.angle()?;
+        Ok(f32::from(angle) * 360.0 / f32::from(ANGLE_MAX))
+    }
+
+    /// Get the angular position in radians, in the range `[0, 2π)`
+    ///
+    /// The raw value is mapped as `raw / ANGLE_MAX * 2π`, so `0x3FFF` maps
+    /// just below 2π rather than exactly to it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    #[cfg(feature = "float")]
+    pub fn angle_radians(&mut self) -> Result<f32, Error<E>> {
+        let angle = self.angle()?;
+        Ok(f32::from(angle) / f32::from(ANGLE_MAX) * core::f32::consts::TAU)
+    }
+
+    /// Get the angular position as a dimensioned angle quantity, in the range
+    /// `[0, 2π)` radians
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    #[cfg(feature = "uom")]
+    pub fn angle_uom(&mut self) -> Result<uom::si::f32::Angle, Error<E>> {
+        self.angle_radians()
+            .map(uom::si::f32::Angle::new::<uom::si::angle::radian>)
+    }
+
+    /// Get the angular position in whole degrees in the range `-180..180`
+    ///
+    /// See [`angle::signed_degrees`] for the mapping and rounding
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn angle_signed_degrees(&mut self) -> Result<i16, Error<E>> {
+        self.angle().map(angle::signed_degrees)
+    }
+
+    /// Get the angular position in radians, in the range `[-π, π)`
+    ///
+    /// Angles below half a revolution map to `[0, π)` and the rest to
+    /// `[-π, 0)`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    #[cfg(feature = "float")]
+    pub fn angle_signed_radians(&mut self) -> Result<f32, Error<E>> {
+        let counts = angle::shortest_delta(0, self.angle()?);
+        Ok(f32::from(counts) / f32::from(ANGLE_MAX) * core::f32::consts::TAU)
     }
 
     /// Get the 14-bit magnitude value from CORDIC
@@ -227,25 +993,262 @@ where
         self.read_register(Register::Mag)
     }
 
+    /// Get the magnitude value normalized to the range `[0, 1]`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    #[cfg(feature = "float")]
+    pub fn magnitude_normalized(&mut self) -> Result<f32, Error<E>> {
+        let magnitude = self.magnitude()?;
+        Ok(f32::from(magnitude) / f32::from(DATA_MASK))
+    }
+
+    /// Check if the magnitude value is at least `threshold`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn magnet_detected(&mut self, threshold: u16) -> Result<bool, Error<E>> {
+        self.magnitude().map(|magnitude| magnitude >= threshold)
+    }
+
+    /// Check whether a magnet is present at all
+    ///
+    /// Returns `false` if the MAGL flag is set (field too weak) or the
+    /// magnitude is below the minimum set with [`Self::set_field_thresholds`],
+    /// which defaults to [`DiagnosticsAgcRegister::MIN_MAGNITUDE`], and
+    /// `true` otherwise. Unlike [`DiagnosticsAgcRegister::is_valid`], a
+    /// field that is too strong (MAGH) or a CORDIC overflow still counts as
+    /// present. The registers are read with [`Self::field_report`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn is_magnet_present(&mut self) -> Result<bool, Error<E>> {
+        let report = self.field_report()?;
+        Ok(!report.magl && report.magnitude >= self.config.min_magnitude)
+    }
+
     /// Get the diagnostics and AGC register
+    ///
+    /// With the `defmt` feature, a warning is logged if the AGC value is
+    /// saturated at 0 or 255, which indicates a marginal magnet placement
+    /// before the MAGH or MAGL flags are set
+    ///
     /// # Errors
     ///
     /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
-    /// ```
     pub fn diagnostics(&mut self) -> Result<DiagnosticsAgcRegister, Error<E>> {
-        self.read_register(Register::DiaAgc)
-            .map(DiagnosticsAgcRegister)
+        let diagnostics = self
+            .read_register(Register::DiaAgc)
+            .map(DiagnosticsAgcRegister)?;
+
+        #[cfg(feature = "defmt")]
+        match diagnostics.agc() {
+            0 => defmt::warn!("AGC saturated at 0, magnet too close"),
+            255 => defmt::warn!("AGC saturated at 255, magnet too far"),
+            agc => defmt::trace!("AGC value: {}", agc),
+        }
+
+        Ok(diagnostics)
     }
 
     /// Clear the error flag by reading the clear error flag register
     ///
+    /// The returned register reports which error caused the flag to be set
+    ///
     /// # Errors
     ///
     /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
     pub fn clear_error_flag(&mut self) -> Result<ErrorFlagRegister, Error<E>> {
-        self.read_register(Register::ErrFl).map(ErrorFlagRegister)
+        let (data, error_flag) = self.read_register_raw(Register::ErrFl)?;
+        if error_flag {
+            return Err(Error::SensorError);
+        }
+
+        Ok(ErrorFlagRegister(data))
     }
 
+    /// Clear the error flag, then read a register
+    ///
+    /// Use this to recover from [`Error::SensorError`] by retrying the failed
+    /// read once. The register is only read once, so a repeated sensor error
+    /// is returned rather than retried
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn clear_and_read(&mut self, register: Register) -> Result<u16, Error<E>> {
+        self.clear_error_flag()?;
+        self.read_register(register)
+    }
+
+    /// Bring the sensor into a known state after startup or a brownout
+    ///
+    /// Clears the error flag and checks that the internal offset compensation
+    /// has finished. This is a one-shot check and does not wait for the
+    /// offset compensation to finish
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotReady`] if the offset compensation has not
+    /// finished, or an error if SPI communication fails, parity check fails,
+    /// or the sensor reports an error
+    pub fn reset(&mut self) -> Result<(), Error<E>> {
+        self.clear_error_flag()?;
+
+        if self.diagnostics()?.lf() {
+            Ok(())
+        } else {
+            Err(Error::NotReady)
+        }
+    }
+
+    /// Wait for the internal offset compensation to finish after power-up
+    ///
+    /// The diagnostics register is polled up to `max_attempts` times (at
+    /// least once), waiting `poll_interval_us` microseconds between polls
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Timeout`] if the offset compensation has not finished
+    /// after the last attempt, or an error if SPI communication fails, parity
+    /// check fails, or the sensor reports an error
+    pub fn wait_until_ready<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        poll_interval_us: u32,
+        max_attempts: u32,
+    ) -> Result<(), Error<E>> {
+        for attempt in 1..=max_attempts.max(1) {
+            if self.diagnostics()?.lf() {
+                return Ok(());
+            }
+
+            if attempt < max_attempts {
+                delay.delay_us(poll_interval_us);
+            }
+        }
+
+        Err(Error::Timeout)
+    }
+
+    /// Set bits of the PROG register without changing the others
+    ///
+    /// The register is read, `(current & !mask) | (value & mask)` is written
+    /// back. See [`ProgrammingRegister`] for the bit masks
+    ///
+    /// **Warning:** setting [`ProgrammingRegister::PROGOTP`] while programming
+    /// is enabled starts a permanent OTP burn
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    #[cfg(feature = "otp-programming")]
+    pub fn set_prog_bits(&mut self, mask: u16, value: u16) -> Result<(), Error<E>> {
+        self.modify_register(Register::Prog, |prog| {
+            *prog = (*prog & !mask) | (value & mask);
+        })
+    }
+
+    /// Permanently program the current non-volatile register contents into
+    /// the OTP memory
+    ///
+    /// The current values of ZPOSM, ZPOSL, SETTINGS1, and SETTINGS2 are
+    /// burned into the OTP memory, then refreshed from OTP with the
+    /// verification guard band enabled and compared against the burned values
+    ///
+    /// **Warning:** the OTP memory can only be programmed once. A failed or
+    /// interrupted burn can leave the sensor permanently misconfigured. Make
+    /// sure the supply voltage meets the programming requirements in the
+    /// datasheet before calling this
+    ///
+    /// The PROG register is polled for completion of the burn up to
+    /// `max_attempts` times (at least once), waiting `poll_interval_us`
+    /// microseconds between polls, so the burn is given a time budget of about
+    /// `poll_interval_us * max_attempts` microseconds
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Timeout`] if the burn does not complete within the
+    /// time budget, [`Error::VerifyFailed`] if a register does not match after
+    /// the burn, or an error if SPI communication fails, parity check fails,
+    /// or the sensor reports an error
+    ///
+    /// A [`Error::Timeout`] must not be retried: the burn has been started and
+    /// may have partly or fully programmed the OTP memory, which can only be
+    /// programmed once
+    #[cfg(feature = "otp-programming")]
+    pub fn burn_otp<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        poll_interval_us: u32,
+        max_attempts: u32,
+    ) -> Result<(), Error<E>> {
+        const REGISTERS: [Register; 4] = [
+            Register::ZPosM,
+            Register::ZPosL,
+            Register::Settings1,
+            Register::Settings2,
+        ];
+
+        let mut expected = [0u16; REGISTERS.len()];
+        for (value, register) in expected.iter_mut().zip(REGISTERS) {
+            *value = self.read_register(register)?;
+        }
+
+        // The PROG register is written with the literal values from the
+        // datasheet procedure, so PROGOTP is written on its own rather than
+        // on top of PROGEN
+        self.write_register(Register::Prog, ProgrammingRegister::PROGEN)?;
+
+        for (value, register) in expected.iter().zip(REGISTERS) {
+            self.write_register(register, *value)?;
+        }
+
+        self.write_register(Register::Prog, ProgrammingRegister::PROGOTP)?;
+
+        let mut burned = false;
+        for attempt in 1..=max_attempts.max(1) {
+            if self.read_register(Register::Prog)? == ProgrammingRegister::PROGEN {
+                burned = true;
+                break;
+            }
+
+            if attempt < max_attempts {
+                delay.delay_us(poll_interval_us);
+            }
+        }
+
+        if !burned {
+            return Err(Error::Timeout);
+        }
+
+        for register in REGISTERS {
+            self.write_register(register, 0)?;
+        }
+
+        self.write_register(Register::Prog, ProgrammingRegister::PROGVER)?;
+        self.write_register(Register::Prog, ProgrammingRegister::OTPREF)?;
+
+        for (expected, register) in expected.into_iter().zip(REGISTERS) {
+            let actual = self.read_register(register)?;
+            if actual != expected {
+                return Err(Error::VerifyFailed { expected, actual });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the 14-bit zero position from the ZPOSM and ZPOSL registers
+    ///
+    /// ZPOSM holds bits 13..6 and the lower 6 bits of ZPOSL hold bits 5..0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
     pub fn zero_position(&mut self) -> Result<u16, Error<E>> {
         let msb = self
             .read_register(Register::ZPosM)
@@ -254,24 +1257,90 @@ where
             .read_register(Register::ZPosL)
             .map(ZeroPositionLsbRegister)?;
 
-        Ok(((msb.zposm() as u16) << 6) | (lsb.zposl() as u16))
+        Ok((u16::from(msb.zposm()) << 6) | u16::from(lsb.zposl()))
     }
 
+    /// Set the 14-bit zero position in the ZPOSM and ZPOSL registers
+    ///
+    /// `value` is masked to 14 bits. The error-flag enable bits in ZPOSL are
+    /// preserved
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    #[allow(clippy::cast_possible_truncation)]
     pub fn set_zero_position(&mut self, value: u16) -> Result<(), Error<E>> {
+        let value = value & DATA_MASK;
         let lsb = value & 0b11_1111;
         let msb = value >> 6;
 
         self.modify_register(Register::ZPosL, |v: &mut u16| {
             let mut r = ZeroPositionLsbRegister(*v);
             r.set_zposl(lsb as u8);
-            *v = r.0
+            *v = r.0;
         })?;
         self.modify_register(Register::ZPosM, |v: &mut u16| {
             let mut r = ZeroPositionMsbRegister(*v);
             r.set_zposm(msb as u8);
-            *v = r.0
+            *v = r.0;
         })?;
 
         Ok(())
     }
+
+    /// Set the current position as the zero position
+    ///
+    /// The zero position is cleared before the angle is read, since the
+    /// measured angle already has the previous zero position applied. The
+    /// programmed value is returned
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn zero_here(&mut self) -> Result<u16, Error<E>> {
+        self.set_zero_position(0)?;
+
+        let angle = self.read_register(Register::AngleCom)?;
+
+        self.set_zero_position(angle)?;
+
+        Ok(angle)
+    }
+}
+
+impl<SPI, P> As5047d<SPI, P>
+where
+    SPI: SpiDevice<u8>,
+    P: ParityScheme,
+{
+    /// Read a register using a caller-provided buffer for both transfers
+    ///
+    /// The first two bytes of `scratch` hold the read command and the last two
+    /// the NOP frame, and each is overwritten with the received bytes. This
+    /// lets the transfer buffers be placed in a DMA-capable memory region.
+    /// The response is interpreted as for any other register read
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI communication fails, parity check fails, or the sensor reports an error
+    pub fn read_register_into(
+        &mut self,
+        register: Register,
+        scratch: &mut [u8; 4],
+    ) -> Result<u16, Error<SPI::Error>> {
+        let (command, response) = scratch.split_at_mut(2);
+        command.copy_from_slice(&utils::read_command::<P>(register).to_be_bytes());
+        response.copy_from_slice(&NOP_COMMAND.to_be_bytes());
+
+        self.primed = false;
+        self.spi
+            .transfer_in_place(command)
+            .map_err(Error::Communication)?;
+        self.spi
+            .transfer_in_place(response)
+            .map_err(Error::Communication)?;
+
+        let response = u16::from_be_bytes([response[0], response[1]]);
+        self.parse_response(response)
+    }
 }
@@ -1,6 +1,7 @@
 /// Error type for AS5047D operations
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Error<E> {
     /// Communication error with the sensor
     Communication(E),
@@ -8,4 +9,61 @@ pub enum Error<E> {
     ParityError,
     /// Error flag set by the sensor (invalid command or parity error)
     SensorError,
+    /// The sensor is not ready (offset compensation not finished)
+    NotReady,
+    /// The measurement is invalid (CORDIC overflow or magnetic field out of
+    /// range)
+    DataInvalid,
+    /// The sensor did not become ready in time
+    Timeout,
+    /// A value read back from the sensor did not match the value written
+    VerifyFailed {
+        /// Value that was written
+        expected: u16,
+        /// Value that was read back
+        actual: u16,
+    },
+    /// A response frame did not have the expected value, which usually means
+    /// the SPI bus is misconfigured
+    UnexpectedResponse(u16),
+}
+
+impl<E: core::fmt::Debug> core::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Communication(e) => write!(f, "SPI communication error: {e:?}"),
+            Self::ParityError => f.write_str("parity error in received data"),
+            Self::SensorError => f.write_str("sensor error flag set"),
+            Self::NotReady => f.write_str("sensor not ready"),
+            Self::DataInvalid => f.write_str("invalid measurement"),
+            Self::Timeout => f.write_str("timed out waiting for sensor"),
+            Self::VerifyFailed { expected, actual } => write!(
+                f,
+                "verification failed: expected 0x{expected:04X}, read 0x{actual:04X}"
+            ),
+            Self::UnexpectedResponse(frame) => write!(f, "unexpected response 0x{frame:04X}"),
+        }
+    }
+}
+
+impl<E: embedded_hal::spi::Error> Error<E> {
+    /// Kind of the underlying SPI error
+    ///
+    /// Returns `None` for errors that did not come from the SPI bus
+    pub fn kind(&self) -> Option<embedded_hal::spi::ErrorKind> {
+        match self {
+            Self::Communication(e) => Some(e.kind()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "core-error")]
+impl<E: core::error::Error + 'static> core::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Communication(e) => Some(e),
+            _ => None,
+        }
+    }
 }
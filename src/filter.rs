@@ -0,0 +1,249 @@
+//! Wrap-aware filters for 14-bit angle readings
+
+use crate::{angle, protocol::DATA_MASK};
+
+#[cfg(feature = "float")]
+use crate::driver::ANGLE_MAX;
+
+/// Exponential moving average filter for angle readings
+///
+/// The filter moves along the shortest arc towards each new reading, so
+/// readings on either side of the 0/16383 boundary do not pull the output
+/// towards the opposite side of the circle
+#[cfg(feature = "float")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AngleFilter {
+    alpha: f32,
+    state: Option<f32>,
+}
+
+#[cfg(feature = "float")]
+impl AngleFilter {
+    /// Create a new filter with smoothing factor `alpha`
+    ///
+    /// `alpha` is clamped to `[0, 1]`. Larger values follow new readings more
+    /// closely, with `1` disabling filtering entirely
+    #[must_use]
+    pub fn new(alpha: f32) -> Self {
+        Self {
+            alpha: alpha.clamp(0.0, 1.0),
+            state: None,
+        }
+    }
+
+    /// Update the filter with a new angle reading and return the filtered angle
+    ///
+    /// The first reading initializes the filter
+    pub fn update(&mut self, raw: u16) -> u16 {
+        let full = f32::from(ANGLE_MAX);
+        let half = full / 2.0;
+        let raw = f32::from(raw);
+
+        let state = match self.state {
+            Some(state) => {
+                let mut delta = raw - state;
+                if delta >= half {
+                    delta -= full;
+                } else if delta < -half {
+                    delta += full;
+                }
+
+                let state = state + self.alpha * delta;
+                if state < 0.0 {
+                    state + full
+                } else if state >= full {
+                    state - full
+                } else {
+                    state
+                }
+            }
+            None => raw,
+        };
+        self.state = Some(state);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let angle = (state + 0.5) as u16 % ANGLE_MAX;
+        angle
+    }
+}
+
+/// Circular mean over a window of the last `N` angle readings
+///
+/// The mean is computed with integer shortest-arc accumulation relative to
+/// the oldest buffered reading, which is exact as long as the readings in
+/// the window span less than half a revolution
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::AngleAverager;
+///
+/// let mut averager = AngleAverager::<4>::new();
+/// for raw in [16382, 16383, 0, 1] {
+///     averager.push(raw);
+/// }
+/// assert_eq!(averager.mean(), Some(0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AngleAverager<const N: usize> {
+    samples: [u16; N],
+    next: usize,
+    len: usize,
+}
+
+impl<const N: usize> AngleAverager<N> {
+    /// Create a new empty averager
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            samples: [0; N],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Add an angle reading, replacing the oldest one once the window is full
+    pub fn push(&mut self, raw: u16) {
+        if N == 0 {
+            return;
+        }
+
+        self.samples[self.next] = raw & DATA_MASK;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Circular mean of the buffered readings, rounded to the nearest count
+    ///
+    /// Returns `None` until `N` readings have been pushed
+    #[must_use]
+    pub fn mean(&self) -> Option<u16> {
+        if N == 0 || self.len < N {
+            return None;
+        }
+
+        // Once full, `next` points at the oldest reading
+        let reference = self.samples[self.next];
+        let sum: i64 = self
+            .samples
+            .iter()
+            .map(|&raw| i64::from(angle::shortest_delta(reference, raw)))
+            .sum();
+
+        let count = i64::try_from(N).ok()?;
+        let delta = (2 * sum + count).div_euclid(2 * count);
+        let delta = i32::try_from(delta).ok()?;
+
+        Some(angle::wrap_add(reference, delta))
+    }
+}
+
+impl<const N: usize> Default for AngleAverager<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Median filter over the last three angle readings
+///
+/// Rejects single-sample spikes without floating point. The median is taken
+/// over the shortest-arc offsets from the newest reading, so readings on
+/// either side of the 0/16383 boundary are ordered correctly
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::MedianFilter3;
+///
+/// let mut filter = MedianFilter3::new();
+/// assert_eq!(filter.update(16382), 16382);
+/// assert_eq!(filter.update(16383), 16382);
+/// assert_eq!(filter.update(8000), 16383);
+/// assert_eq!(filter.update(1), 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MedianFilter3 {
+    samples: Option<[u16; 3]>,
+}
+
+impl MedianFilter3 {
+    /// Create a new filter
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { samples: None }
+    }
+
+    /// Update the filter with a new angle reading and return the median of
+    /// the last three readings
+    ///
+    /// The first reading fills the whole window
+    pub fn update(&mut self, raw: u16) -> u16 {
+        let raw = raw & DATA_MASK;
+
+        let [a, b, _] = match self.samples {
+            Some([_, b, c]) => [b, c, raw],
+            None => [raw; 3],
+        };
+        self.samples = Some([a, b, raw]);
+
+        let a = angle::shortest_delta(raw, a);
+        let b = angle::shortest_delta(raw, b);
+        let median = a.min(b).max(a.max(b).min(0));
+
+        angle::wrap_add(raw, i32::from(median))
+    }
+}
+
+/// Limits how fast an angle can change, moving along the shortest arc
+/// towards each target by at most a fixed number of counts per step
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::SlewLimiter;
+///
+/// let mut limiter = SlewLimiter::new(100);
+/// assert_eq!(limiter.step(16300), 16300);
+/// assert_eq!(limiter.step(200), 16);
+/// assert_eq!(limiter.step(200), 116);
+/// assert_eq!(limiter.step(200), 200);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SlewLimiter {
+    max_step: u16,
+    output: Option<u16>,
+}
+
+impl SlewLimiter {
+    /// Create a new limiter moving at most `max_step` counts per step
+    #[must_use]
+    pub const fn new(max_step: u16) -> Self {
+        Self {
+            max_step,
+            output: None,
+        }
+    }
+
+    /// Move the output towards `target` and return it
+    ///
+    /// The first call sets the output to `target` directly
+    pub fn step(&mut self, target: u16) -> u16 {
+        let target = target & DATA_MASK;
+
+        let output = match self.output {
+            Some(output) => {
+                let max_step = i32::from(self.max_step);
+                let delta = i32::from(angle::shortest_delta(output, target));
+                angle::wrap_add(output, delta.clamp(-max_step, max_step))
+            }
+            None => target,
+        };
+        self.output = Some(output);
+
+        output
+    }
+}
@@ -0,0 +1,100 @@
+//! Sector selection with hysteresis for rotary-switch use
+
+use crate::{angle, driver::ANGLE_MAX, protocol::DATA_MASK};
+
+/// Maps angle readings onto equal sectors, only changing sector once the
+/// angle has moved more than a deadband past the current sector's boundary
+///
+/// The first and last sectors are adjacent across the 0/16383 boundary
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::Hysteresis;
+///
+/// // Four sectors of 4096 counts with a deadband of 100 counts
+/// let mut selector = Hysteresis::new(4, 100);
+/// assert_eq!(selector.position(4000), 0);
+///
+/// // Within the deadband on either side of the boundary at 4096
+/// assert_eq!(selector.position(4146), 0);
+/// assert_eq!(selector.position(4196), 1);
+/// assert_eq!(selector.position(4050), 1);
+/// assert_eq!(selector.position(3990), 0);
+///
+/// // Across the seam between the last and first sector
+/// assert_eq!(selector.position(16000), 3);
+/// assert_eq!(selector.position(50), 3);
+/// assert_eq!(selector.position(150), 0);
+/// assert_eq!(selector.position(16350), 0);
+/// assert_eq!(selector.position(16200), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Hysteresis {
+    positions: u16,
+    deadband: u16,
+    current: Option<u16>,
+}
+
+impl Hysteresis {
+    /// Create a new selector with `positions` sectors and a deadband in counts
+    ///
+    /// `positions` is clamped to at least 1
+    #[must_use]
+    pub const fn new(positions: u16, deadband: u16) -> Self {
+        Self {
+            positions: if positions == 0 { 1 } else { positions },
+            deadband,
+            current: None,
+        }
+    }
+
+    /// Update the selector with an angle reading and return the current sector
+    pub fn position(&mut self, raw: u16) -> u16 {
+        let raw = raw & DATA_MASK;
+        let candidate = self.sector_of(raw);
+
+        let Some(current) = self.current else {
+            self.current = Some(candidate);
+            return candidate;
+        };
+
+        if candidate != current && self.distance_outside(current, raw) > self.deadband {
+            self.current = Some(candidate);
+            return candidate;
+        }
+
+        current
+    }
+
+    fn sector_of(self, raw: u16) -> u16 {
+        #[allow(clippy::cast_possible_truncation)]
+        let sector = angle::scale(raw, u32::from(self.positions)) as u16;
+        sector
+    }
+
+    /// First count of a sector, where `sector == positions` wraps to 16384
+    fn sector_start(self, sector: u16) -> u32 {
+        (u32::from(sector) * u32::from(ANGLE_MAX)).div_ceil(u32::from(self.positions))
+    }
+
+    /// Distance in counts from `raw` to the nearest edge of `sector`
+    fn distance_outside(self, sector: u16, raw: u16) -> u16 {
+        let start = self.sector_start(sector);
+        let width = self.sector_start(sector + 1) - start;
+
+        let full = u32::from(ANGLE_MAX);
+        let relative = (u32::from(raw) + full - start) % full;
+        if relative < width {
+            return 0;
+        }
+
+        let past_end = relative - width + 1;
+        let before_start = full - relative;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let distance = past_end.min(before_start) as u16;
+        distance
+    }
+}
@@ -2,11 +2,59 @@
 #![forbid(unsafe_code)]
 #![warn(clippy::pedantic)]
 
+pub mod angle;
+mod builder;
+mod bus;
+mod change;
+mod daisy_chain;
 mod driver;
 mod error;
+mod filter;
+mod hysteresis;
+mod linearity;
+mod multi_turn;
+pub mod parity;
+pub mod protocol;
+mod quadrature;
+mod range;
 mod register;
+mod sampler;
+mod snapshot;
+mod stall;
+#[cfg(feature = "stream")]
+mod stream;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+mod transfer;
 mod utils;
+mod velocity;
 
+pub use builder::{As5047dBuilder, ErrorClearPolicy, OffsetMode};
+pub use bus::{As5047dBus, ChipSelectDevice, ChipSelectError, DelayedDevice};
+pub use change::ChangeDetector;
+pub use daisy_chain::DaisyChain;
 pub use driver::{ANGLE_MAX, As5047d};
 pub use error::Error;
-pub use register::Register;
+#[cfg(feature = "float")]
+pub use filter::AngleFilter;
+pub use filter::{AngleAverager, MedianFilter3, SlewLimiter};
+pub use hysteresis::Hysteresis;
+pub use linearity::{LinearityRecorder, LinearityReport};
+pub use multi_turn::MultiTurn;
+pub use parity::{EvenParity, ParityScheme};
+pub use protocol::{MAX_SPI_HZ, MIN_CS_HIGH_NS, SpiFrequencyError, validate_spi_frequency};
+pub use quadrature::QuadratureEmulator;
+pub use range::{RangeMode, RangeTracker};
+#[cfg(feature = "bitflags")]
+pub use register::DiagnosticFlags;
+pub use register::{
+    DiagnosticsAgcRegister, DiagnosticsFields, ErrorFlagRegister, MagnetHealth,
+    ProgrammingRegister, Register, Validity,
+};
+pub use sampler::AdaptiveSampler;
+pub use snapshot::{FieldReport, SensorSnapshot};
+pub use stall::StallDetector;
+#[cfg(feature = "stream")]
+pub use stream::AngleStream;
+pub use transfer::{FrameTransfer, Word16};
+pub use velocity::VelocityTracker;
@@ -0,0 +1,107 @@
+//! Linearity accounting for sensor characterization
+
+use crate::angle;
+
+/// Summary of the errors recorded by a [`LinearityRecorder`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LinearityReport {
+    /// Number of recorded points
+    pub points: u32,
+    /// Largest absolute error in counts
+    pub max_deviation: u16,
+    /// Most negative signed error in counts
+    pub min_error: i16,
+    /// Most positive signed error in counts
+    pub max_error: i16,
+    /// Mean signed error in counts, rounded towards negative infinity
+    pub mean_error: i16,
+}
+
+/// Records (reference, measured) angle pairs and accumulates the
+/// shortest-arc error of each, for example while turning a calibrated
+/// fixture
+///
+/// Only summary statistics are kept, so any number of points can be
+/// recorded without allocation
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::LinearityRecorder;
+///
+/// let mut recorder = LinearityRecorder::new();
+/// recorder.record(0, 16380);
+/// recorder.record(8192, 8194);
+/// recorder.record(16383, 1);
+///
+/// let report = recorder.report();
+/// assert_eq!(report.points, 3);
+/// assert_eq!(report.max_deviation, 4);
+/// assert_eq!(report.min_error, -4);
+/// assert_eq!(report.max_error, 2);
+/// assert_eq!(report.mean_error, 0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LinearityRecorder {
+    points: u32,
+    min_error: i16,
+    max_error: i16,
+    sum: i64,
+}
+
+impl LinearityRecorder {
+    /// Create a new empty recorder
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            points: 0,
+            min_error: 0,
+            max_error: 0,
+            sum: 0,
+        }
+    }
+
+    /// Record a measured angle against its reference angle
+    pub fn record(&mut self, reference_raw: u16, measured_raw: u16) {
+        let error = angle::shortest_delta(reference_raw, measured_raw);
+
+        if self.points == 0 {
+            self.min_error = error;
+            self.max_error = error;
+        } else {
+            self.min_error = self.min_error.min(error);
+            self.max_error = self.max_error.max(error);
+        }
+
+        self.points = self.points.saturating_add(1);
+        self.sum += i64::from(error);
+    }
+
+    /// Largest absolute shortest-arc error in counts over all recorded points
+    #[must_use]
+    pub fn max_deviation(&self) -> u16 {
+        self.min_error
+            .unsigned_abs()
+            .max(self.max_error.unsigned_abs())
+    }
+
+    /// Summary of all recorded points
+    #[must_use]
+    pub fn report(&self) -> LinearityReport {
+        let mean_error = if self.points == 0 {
+            0
+        } else {
+            self.sum.div_euclid(i64::from(self.points))
+        };
+
+        LinearityReport {
+            points: self.points,
+            max_deviation: self.max_deviation(),
+            min_error: self.min_error,
+            max_error: self.max_error,
+            mean_error: i16::try_from(mean_error).unwrap_or_default(),
+        }
+    }
+}
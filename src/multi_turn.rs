@@ -0,0 +1,55 @@
+//! Multi-turn position accumulation across angle readings
+
+use crate::{angle, driver::ANGLE_MAX};
+
+/// Accumulates successive 14-bit angle readings into a position that does
+/// not wrap at the end of each revolution
+///
+/// Readings must be taken often enough that the rotor moves less than half a
+/// revolution between them, otherwise the direction of travel is ambiguous
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MultiTurn {
+    total: i64,
+    last: Option<u16>,
+}
+
+impl MultiTurn {
+    /// Create a new multi-turn accumulator
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            total: 0,
+            last: None,
+        }
+    }
+
+    /// Update the accumulator with a new angle reading
+    ///
+    /// The first reading sets the starting position. Returns the accumulated
+    /// position in counts
+    pub fn update(&mut self, raw: u16) -> i64 {
+        self.total = match self.last {
+            Some(last) => self.total + i64::from(angle::shortest_delta(last, raw)),
+            None => i64::from(raw),
+        };
+        self.last = Some(raw);
+
+        self.total
+    }
+
+    /// Get the number of complete revolutions, rounded towards negative
+    /// infinity
+    #[must_use]
+    pub fn turns(&self) -> i32 {
+        #[allow(clippy::cast_possible_truncation)]
+        let turns = self.total.div_euclid(i64::from(ANGLE_MAX)) as i32;
+        turns
+    }
+
+    /// Get the accumulated position in counts
+    #[must_use]
+    pub fn total_counts(&self) -> i64 {
+        self.total
+    }
+}
@@ -0,0 +1,73 @@
+//! Parity computation used for SPI frames
+
+use crate::{protocol::PARITY_BIT, utils};
+
+/// Compute the even parity bit for the lower 15 bits of a frame
+///
+/// Returns `true` if the lower 15 bits contain an odd number of ones, in
+/// which case the parity bit (bit 15) must be set so the whole frame has an
+/// even number of ones
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::parity;
+///
+/// assert!(!parity::calculate(0x0000));
+/// assert!(parity::calculate(0x0001));
+/// assert!(parity::calculate(0x7FFF));
+/// assert!(!parity::calculate(0x8003));
+/// ```
+#[must_use]
+pub const fn calculate(frame: u16) -> bool {
+    utils::calculate_parity(frame)
+}
+
+/// Check that a frame, including its parity bit, has even parity
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::parity;
+///
+/// assert!(parity::verify(0x0000));
+/// assert!(parity::verify(0xFFFF));
+/// assert!(!parity::verify(0x7FFF));
+/// ```
+#[must_use]
+pub const fn verify(frame: u16) -> bool {
+    utils::verify_parity(frame)
+}
+
+/// Parity computation used by the driver for command and response frames
+///
+/// Production code never needs to implement this: the driver defaults to
+/// [`EvenParity`], which is what the sensor uses. Substituting a scheme that
+/// deliberately miscomputes parity is useful to exercise error handling in
+/// fault injection tests
+pub trait ParityScheme {
+    /// Compute the parity bit for the lower 15 bits of a frame
+    #[must_use]
+    fn calculate(frame: u16) -> bool;
+
+    /// Check the parity bit of a received frame
+    #[must_use]
+    fn verify(frame: u16) -> bool {
+        Self::calculate(frame) == (frame & PARITY_BIT != 0)
+    }
+}
+
+/// Even parity over all 16 bits of a frame, as used by the sensor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EvenParity;
+
+impl ParityScheme for EvenParity {
+    fn calculate(frame: u16) -> bool {
+        calculate(frame)
+    }
+
+    fn verify(frame: u16) -> bool {
+        verify(frame)
+    }
+}
@@ -0,0 +1,84 @@
+//! Bit masks and command constants of the AS5047D SPI frame format
+
+use crate::{parity, register::Register};
+
+/// Read/write bit of a command frame, set for reads
+pub const READ_BIT: u16 = 0x4000;
+/// Even parity bit of a frame
+pub const PARITY_BIT: u16 = 0x8000;
+/// Error flag bit of a response frame
+pub const ERROR_FLAG: u16 = 0x4000;
+/// Mask of the 14-bit address or data field of a frame
+pub const DATA_MASK: u16 = 0x3FFF;
+/// NOP command frame
+pub const NOP_COMMAND: u16 = 0x0000;
+
+/// Minimum time in nanoseconds chip select must stay high between frames
+pub const MIN_CS_HIGH_NS: u32 = 350;
+/// Maximum SPI clock frequency in Hz
+pub const MAX_SPI_HZ: u32 = 10_000_000;
+
+/// SPI clock frequency below which reading the sensor wastes loop time
+#[cfg(feature = "defmt")]
+const LOW_SPI_HZ: u32 = 1_000_000;
+
+/// Error returned by [`validate_spi_frequency`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SpiFrequencyError {
+    /// The frequency is zero
+    Zero,
+    /// The frequency is above [`MAX_SPI_HZ`]
+    TooHigh,
+}
+
+/// Check that an SPI clock frequency in Hz is supported by the sensor
+///
+/// Logs a warning with `defmt` if the frequency is valid but below 1MHz
+///
+/// # Errors
+///
+/// Returns an error if the frequency is zero or above [`MAX_SPI_HZ`]
+pub fn validate_spi_frequency(hz: u32) -> Result<(), SpiFrequencyError> {
+    if hz == 0 {
+        return Err(SpiFrequencyError::Zero);
+    }
+
+    if hz > MAX_SPI_HZ {
+        return Err(SpiFrequencyError::TooHigh);
+    }
+
+    #[cfg(feature = "defmt")]
+    if hz < LOW_SPI_HZ {
+        defmt::warn!("SPI frequency of {}Hz is unusually low", hz);
+    }
+
+    Ok(())
+}
+
+/// Bytes to transmit to read a register, with the parity bit set
+///
+/// This is a `const fn`, so command buffers can be built at compile time,
+/// for example for DMA transfers
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::{Register, protocol::read_command_frame};
+///
+/// static ANGLE_COMMAND: [u8; 2] = read_command_frame(Register::AngleCom);
+/// assert_eq!(ANGLE_COMMAND, [0xFF, 0xFF]);
+/// ```
+#[must_use]
+pub const fn read_command_frame(register: Register) -> [u8; 2] {
+    // The parity scheme helpers are not const, so the even parity bit is set
+    // directly here
+    let command = READ_BIT | register as u16;
+    let command = if parity::calculate(command) {
+        PARITY_BIT | command
+    } else {
+        command
+    };
+
+    command.to_be_bytes()
+}
@@ -0,0 +1,55 @@
+//! Incremental count emulation from absolute angle readings
+
+use crate::{driver::ANGLE_MAX, multi_turn::MultiTurn};
+
+/// Converts successive 14-bit angle readings into incremental counts, as
+/// produced by a quadrature encoder with a configurable resolution
+///
+/// Readings must be taken often enough that the rotor moves less than half a
+/// revolution between them, otherwise the direction of travel is ambiguous.
+/// Fractional counts are carried over between updates, so no counts are lost
+/// to rounding
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::QuadratureEmulator;
+///
+/// let mut quadrature = QuadratureEmulator::new(4096);
+/// assert_eq!(quadrature.update(16380), 0);
+/// assert_eq!(quadrature.update(4), 2);
+/// assert_eq!(quadrature.update(16380), -2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct QuadratureEmulator {
+    counts_per_rev: u32,
+    position: MultiTurn,
+    count: Option<i128>,
+}
+
+impl QuadratureEmulator {
+    /// Create a new emulator producing `counts_per_rev` counts per revolution
+    #[must_use]
+    pub const fn new(counts_per_rev: u32) -> Self {
+        Self {
+            counts_per_rev,
+            position: MultiTurn::new(),
+            count: None,
+        }
+    }
+
+    /// Update the emulator with a new angle reading
+    ///
+    /// Returns the signed number of counts since the previous reading. The
+    /// first reading sets the starting position and returns 0
+    pub fn update(&mut self, raw: u16) -> i32 {
+        let position = i128::from(self.position.update(raw));
+        let count = (position * i128::from(self.counts_per_rev)).div_euclid(i128::from(ANGLE_MAX));
+
+        let delta = self.count.map_or(0, |last| count - last);
+        self.count = Some(count);
+
+        i32::try_from(delta).unwrap_or(if delta < 0 { i32::MIN } else { i32::MAX })
+    }
+}
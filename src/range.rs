@@ -0,0 +1,79 @@
+//! Tracking of the extremes of observed angle readings
+
+use crate::{driver::ANGLE_MAX, multi_turn::MultiTurn};
+
+/// How [`RangeTracker`] interprets successive readings
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RangeMode {
+    /// Track the extremes of the raw 14-bit readings
+    #[default]
+    Wrapped,
+    /// Track the extremes of the unwrapped multi-turn position, so a sweep
+    /// through the 0/16383 boundary is captured as a continuous range
+    Unwrapped,
+}
+
+/// Records the minimum and maximum angle seen over a sweep
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RangeTracker {
+    mode: RangeMode,
+    position: MultiTurn,
+    extremes: Option<(i64, i64)>,
+}
+
+impl RangeTracker {
+    /// Create a new range tracker
+    #[must_use]
+    pub const fn new(mode: RangeMode) -> Self {
+        Self {
+            mode,
+            position: MultiTurn::new(),
+            extremes: None,
+        }
+    }
+
+    /// Record an angle reading
+    pub fn observe(&mut self, raw: u16) {
+        let position = match self.mode {
+            RangeMode::Wrapped => i64::from(raw),
+            RangeMode::Unwrapped => self.position.update(raw),
+        };
+
+        self.extremes = Some(match self.extremes {
+            Some((min, max)) => (min.min(position), max.max(position)),
+            None => (position, position),
+        });
+    }
+
+    /// Get the raw angle at the minimum position seen, or 0 if nothing has
+    /// been observed
+    #[must_use]
+    pub fn min(&self) -> u16 {
+        self.extremes.map_or(0, |(min, _)| Self::to_raw(min))
+    }
+
+    /// Get the raw angle at the maximum position seen, or 0 if nothing has
+    /// been observed
+    #[must_use]
+    pub fn max(&self) -> u16 {
+        self.extremes.map_or(0, |(_, max)| Self::to_raw(max))
+    }
+
+    /// Get the distance in counts between the minimum and maximum positions
+    ///
+    /// In [`RangeMode::Unwrapped`] this can exceed a full revolution and
+    /// saturates at `u16::MAX`
+    #[must_use]
+    pub fn span(&self) -> u16 {
+        self.extremes
+            .map_or(0, |(min, max)| u16::try_from(max - min).unwrap_or(u16::MAX))
+    }
+
+    fn to_raw(position: i64) -> u16 {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let raw = position.rem_euclid(i64::from(ANGLE_MAX)) as u16;
+        raw
+    }
+}
@@ -1,8 +1,9 @@
 //! Register addresses for AS5047D sensor.
 
 /// Register addresses for AS5047D
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 #[repr(u16)]
 pub enum Register {
@@ -38,6 +39,45 @@ impl From<Register> for u16 {
     }
 }
 
+// Register addresses from the datasheet. A wrong address is hard to notice
+// in testing, since each response carries the previously addressed register
+const _: () = {
+    assert!(Register::Nop as u16 == 0x0000);
+    assert!(Register::ErrFl as u16 == 0x0001);
+    assert!(Register::Prog as u16 == 0x0003);
+    assert!(Register::ZPosM as u16 == 0x0016);
+    assert!(Register::ZPosL as u16 == 0x0017);
+    assert!(Register::Settings1 as u16 == 0x0018);
+    assert!(Register::Settings2 as u16 == 0x0019);
+    assert!(Register::DiaAgc as u16 == 0x3FFC);
+    assert!(Register::Mag as u16 == 0x3FFD);
+    assert!(Register::AngleUnc as u16 == 0x3FFE);
+    assert!(Register::AngleCom as u16 == 0x3FFF);
+};
+
+/// Implement serde traits for register types as their raw `u16` value
+#[cfg(feature = "serde")]
+macro_rules! impl_serde_raw {
+    ($($register:ident),*) => {
+        $(
+            impl serde::Serialize for $register {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_u16(self.0)
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $register {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    u16::deserialize(deserializer).map(Self)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "serde")]
+impl_serde_raw!(ErrorFlagRegister, DiagnosticsAgcRegister);
+
 bitfield::bitfield! {
     /// ERRFL
     ///
@@ -74,8 +114,20 @@ bitfield::bitfield! {
     pub progen, set_progen: 0;
 }
 
+impl ProgrammingRegister {
+    /// Mask of the program verify bit
+    pub const PROGVER: u16 = 1 << 6;
+    /// Mask of the start OTP programming (burn) bit
+    pub const PROGOTP: u16 = 1 << 3;
+    /// Mask of the OTP refresh bit
+    pub const OTPREF: u16 = 1 << 2;
+    /// Mask of the program OTP enable bit
+    pub const PROGEN: u16 = 1 << 0;
+}
+
 bitfield::bitfield! {
     /// DIAAGC
+    #[derive(Clone, Copy, PartialEq, Eq)]
     pub struct DiagnosticsAgcRegister(u16);
     impl Debug;
     u8;
@@ -97,17 +149,207 @@ bitfield::bitfield! {
 impl DiagnosticsAgcRegister {
     /// Check if the magnetic field strength is within acceptable range
     #[must_use]
-    #[inline(always)]
+    #[inline]
     pub fn magnetic_field_ok(&self) -> bool {
         !self.magh() && !self.magl()
     }
 
     /// Check if data is valid
     #[must_use]
-    #[inline(always)]
+    #[inline]
     pub fn is_valid(&self) -> bool {
         !self.cof() && self.magnetic_field_ok()
     }
+
+    /// Check if data is valid and the CORDIC magnitude is at least
+    /// `min_magnitude`
+    ///
+    /// Use this when the magnitude register has already been read, for
+    /// example through [`As5047d::read_all`](crate::As5047d::read_all).
+    /// [`Self::MIN_MAGNITUDE`] is a reasonable starting threshold
+    #[must_use]
+    pub fn is_valid_with_magnitude(&self, magnitude: u16, min_magnitude: u16) -> bool {
+        self.is_valid() && magnitude >= min_magnitude
+    }
+
+    /// Suggested minimum magnitude for [`Self::is_valid_with_magnitude`].
+    /// This is a heuristic, not a datasheet limit
+    pub const MIN_MAGNITUDE: u16 = 1000;
+
+    /// Get the reason the measurement is invalid, if any
+    ///
+    /// If several flags are set, a CORDIC overflow takes precedence over the
+    /// field strength flags
+    #[must_use]
+    pub fn validity(&self) -> Validity {
+        if self.cof() {
+            Validity::CordicOverflow
+        } else if self.magh() {
+            Validity::FieldTooStrong
+        } else if self.magl() {
+            Validity::FieldTooWeak
+        } else {
+            Validity::Ok
+        }
+    }
+
+    /// AGC values below this are considered too close. This is a heuristic,
+    /// not a datasheet limit
+    pub const AGC_TOO_CLOSE: u8 = 30;
+
+    /// AGC values above this are considered too far. This is a heuristic,
+    /// not a datasheet limit
+    pub const AGC_TOO_FAR: u8 = 220;
+
+    /// Estimate whether the magnet is positioned correctly
+    ///
+    /// A low AGC value indicates a strong field (magnet close) and a high AGC
+    /// value indicates a weak field (magnet far). The MAGH and MAGL flags take
+    /// precedence over the AGC thresholds
+    #[must_use]
+    pub fn agc_health(&self) -> MagnetHealth {
+        let agc = self.agc();
+
+        if self.magh() || agc < Self::AGC_TOO_CLOSE {
+            MagnetHealth::TooClose
+        } else if self.magl() || agc > Self::AGC_TOO_FAR {
+            MagnetHealth::TooFar
+        } else {
+            MagnetHealth::Good
+        }
+    }
+
+    /// AGC value as a 0-100 placement quality score
+    ///
+    /// The score is 100 at the middle of the band between
+    /// [`Self::AGC_TOO_CLOSE`] and [`Self::AGC_TOO_FAR`], and falls off
+    /// linearly to 0 at both thresholds. AGC values outside the band score 0
+    #[must_use]
+    pub fn agc_percent(&self) -> u8 {
+        let center = Self::AGC_TOO_CLOSE.midpoint(Self::AGC_TOO_FAR);
+        let half_width = u16::from(center - Self::AGC_TOO_CLOSE);
+        let distance = u16::from(self.agc().abs_diff(center)).min(half_width);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let percent = (100 - distance * 100 / half_width) as u8;
+        percent
+    }
+
+    /// Get the diagnostic flags packed into the low nibble of a byte
+    ///
+    /// The bits keep their order from the register, shifted down by 8:
+    /// - bit 3: MAGL
+    /// - bit 2: MAGH
+    /// - bit 1: COF
+    /// - bit 0: LF
+    ///
+    /// Bits 7-4 are always zero
+    #[must_use]
+    pub fn flags_byte(&self) -> u8 {
+        #[allow(clippy::cast_possible_truncation)]
+        let flags = ((self.0 >> 8) & 0x0F) as u8;
+        flags
+    }
+
+    /// Get all fields of the register as plain values
+    #[must_use]
+    pub fn decompose(&self) -> DiagnosticsFields {
+        DiagnosticsFields {
+            magl: self.magl(),
+            magh: self.magh(),
+            cof: self.cof(),
+            lf: self.lf(),
+            agc: self.agc(),
+        }
+    }
+
+    /// Get the diagnostic flags as a flag set
+    #[cfg(feature = "bitflags")]
+    #[must_use]
+    #[inline]
+    pub fn flags(&self) -> DiagnosticFlags {
+        DiagnosticFlags::from_bits_truncate(self.0)
+    }
+}
+
+impl core::fmt::Display for DiagnosticsAgcRegister {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "AGC={}", self.agc())?;
+
+        for (set, name) in [
+            (self.lf(), "LF"),
+            (self.cof(), "COF"),
+            (self.magh(), "MAGH"),
+            (self.magl(), "MAGL"),
+        ] {
+            if set {
+                write!(f, " {name}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Fields of the diagnostics and AGC register as plain values, see
+/// [`DiagnosticsAgcRegister::decompose`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(clippy::struct_excessive_bools)]
+pub struct DiagnosticsFields {
+    /// Magnetic field strength too low
+    pub magl: bool,
+    /// Magnetic field strength too high
+    pub magh: bool,
+    /// CORDIC overflow
+    pub cof: bool,
+    /// Offset compensation finished
+    pub lf: bool,
+    /// Automatic gain control value
+    pub agc: u8,
+}
+
+/// Validity of a measurement, see [`DiagnosticsAgcRegister::validity`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Validity {
+    /// The measurement is valid
+    Ok,
+    /// CORDIC overflow
+    CordicOverflow,
+    /// Magnetic field strength too high (MAGH)
+    FieldTooStrong,
+    /// Magnetic field strength too low (MAGL)
+    FieldTooWeak,
+}
+
+/// Magnet placement estimated from the AGC value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MagnetHealth {
+    /// The magnetic field is too strong
+    TooClose,
+    /// The magnetic field is within the expected range
+    Good,
+    /// The magnetic field is too weak
+    TooFar,
+}
+
+#[cfg(feature = "bitflags")]
+bitflags::bitflags! {
+    /// Diagnostic flags of the DIAAGC register
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct DiagnosticFlags: u16 {
+        /// Magnetic field strength too low
+        const MAGL = 1 << 11;
+        /// Magnetic field strength too high
+        const MAGH = 1 << 10;
+        /// CORDIC overflow
+        const COF = 1 << 9;
+        /// Offset compensation finished
+        const LF = 1 << 8;
+    }
 }
 
 bitfield::bitfield! {
@@ -166,7 +408,7 @@ bitfield::bitfield! {
     pub struct Settings1Register(u16);
     impl Debug;
     u8;
-    /// Enables PWM (setting of UVW_ABI Bit necessary)
+    /// Enables PWM (setting of `UVW_ABI` Bit necessary)
     pub pwmon, set_pwmon: 7;
     /// This bit defines which data can be read form address 0x3FFF.
     ///
@@ -0,0 +1,68 @@
+//! Velocity-dependent sampling interval recommendation
+
+/// Recommends the delay until the next angle reading based on the current
+/// angular velocity, so a scheduler can sample quickly while the rotor
+/// spins and save power while it is idle
+///
+/// The delay is mapped from the absolute velocity in counts per second, for
+/// example from [`VelocityTracker`](crate::VelocityTracker):
+/// - at or below `idle_cps`, the delay is `max_delay_us`
+/// - at or above `fast_cps`, the delay is `min_delay_us`
+/// - in between, the delay is interpolated linearly
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::AdaptiveSampler;
+///
+/// let sampler = AdaptiveSampler::new(100, 10_000, 1_000, 101_000);
+/// assert_eq!(sampler.next_delay_us(0), 10_000);
+/// assert_eq!(sampler.next_delay_us(-51_000), 5_050);
+/// assert_eq!(sampler.next_delay_us(200_000), 100);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AdaptiveSampler {
+    min_delay_us: u32,
+    max_delay_us: u32,
+    idle_cps: u32,
+    fast_cps: u32,
+}
+
+impl AdaptiveSampler {
+    /// Create a new sampler
+    ///
+    /// The delay bounds and velocity breakpoints are swapped if given in the
+    /// wrong order
+    #[must_use]
+    pub fn new(min_delay_us: u32, max_delay_us: u32, idle_cps: u32, fast_cps: u32) -> Self {
+        Self {
+            min_delay_us: min_delay_us.min(max_delay_us),
+            max_delay_us: min_delay_us.max(max_delay_us),
+            idle_cps: idle_cps.min(fast_cps),
+            fast_cps: idle_cps.max(fast_cps),
+        }
+    }
+
+    /// Recommended delay in microseconds before the next reading at a
+    /// velocity of `velocity_cps` counts per second
+    #[must_use]
+    pub fn next_delay_us(&self, velocity_cps: i32) -> u32 {
+        let speed = velocity_cps.unsigned_abs();
+
+        if speed <= self.idle_cps {
+            return self.max_delay_us;
+        }
+        if speed >= self.fast_cps {
+            return self.min_delay_us;
+        }
+
+        let span = u64::from(self.max_delay_us - self.min_delay_us);
+        let progress = u64::from(speed - self.idle_cps);
+        let range = u64::from(self.fast_cps - self.idle_cps);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let reduction = (span * progress / range) as u32;
+        self.max_delay_us - reduction
+    }
+}
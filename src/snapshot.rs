@@ -0,0 +1,60 @@
+//! Combined reading of the sensor status registers
+
+use crate::register::DiagnosticsAgcRegister;
+
+/// Angle, magnitude, and diagnostics read together by
+/// [`As5047d::read_all`](crate::As5047d::read_all)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SensorSnapshot {
+    /// 14-bit corrected angular position
+    pub angle: u16,
+    /// 14-bit CORDIC magnitude
+    pub magnitude: u16,
+    /// Diagnostics and AGC register
+    pub diagnostics: DiagnosticsAgcRegister,
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for SensorSnapshot {
+    fn format(&self, f: defmt::Formatter) {
+        let diagnostics = &self.diagnostics;
+
+        defmt::write!(
+            f,
+            "angle={} mag={} agc={} [",
+            self.angle,
+            self.magnitude,
+            diagnostics.agc()
+        );
+
+        let mut separator = "";
+        for (set, name) in [
+            (diagnostics.lf(), "LF"),
+            (diagnostics.cof(), "COF"),
+            (diagnostics.magh(), "MAGH"),
+            (diagnostics.magl(), "MAGL"),
+        ] {
+            if set {
+                defmt::write!(f, "{=str}{=str}", separator, name);
+                separator = " ";
+            }
+        }
+
+        defmt::write!(f, "]");
+    }
+}
+
+/// Magnetic field strength readings read together by
+/// [`As5047d::field_report`](crate::As5047d::field_report)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FieldReport {
+    /// 14-bit CORDIC magnitude
+    pub magnitude: u16,
+    /// Automatic gain control value
+    pub agc: u8,
+    /// Magnetic field strength too high
+    pub magh: bool,
+    /// Magnetic field strength too low
+    pub magl: bool,
+}
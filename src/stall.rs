@@ -0,0 +1,73 @@
+//! Detection of an angle that stops changing
+
+use crate::{angle, protocol::DATA_MASK};
+
+/// Detects an angle that has stayed within a tolerance for a number of
+/// consecutive readings
+///
+/// This is a liveness check for when the rotor is expected to move, such as
+/// a detached magnet or a hung sensor that keeps reporting a valid angle.
+/// The tolerance is measured along the shortest arc from the first reading of
+/// the current run
+///
+/// # Examples
+///
+/// ```
+/// use as5047d::StallDetector;
+///
+/// let mut detector = StallDetector::new(2, 3);
+/// assert!(!detector.update(16383));
+/// assert!(!detector.update(0));
+/// assert!(detector.update(1));
+/// assert!(!detector.update(100));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StallDetector {
+    tolerance: u16,
+    samples: u16,
+    reference: Option<u16>,
+    count: u16,
+}
+
+impl StallDetector {
+    /// Create a new detector reporting a stall once `samples` consecutive
+    /// readings stay within `tolerance` counts
+    #[must_use]
+    pub const fn new(tolerance: u16, samples: u16) -> Self {
+        Self {
+            tolerance,
+            samples,
+            reference: None,
+            count: 0,
+        }
+    }
+
+    /// Update the detector with an angle reading
+    ///
+    /// Returns `true` while the last `samples` readings, including this one,
+    /// stayed within the tolerance
+    pub fn update(&mut self, raw: u16) -> bool {
+        let raw = raw & DATA_MASK;
+
+        match self.reference {
+            Some(reference)
+                if angle::shortest_delta(reference, raw).unsigned_abs() <= self.tolerance =>
+            {
+                self.count = self.count.saturating_add(1);
+            }
+            _ => {
+                self.reference = Some(raw);
+                self.count = 1;
+            }
+        }
+
+        self.count >= self.samples
+    }
+
+    /// Forget the current run of readings
+    pub fn reset(&mut self) {
+        self.reference = None;
+        self.count = 0;
+    }
+}
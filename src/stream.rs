@@ -0,0 +1,55 @@
+//! Stream of angle samples
+
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::{
+    driver::As5047d,
+    error::Error,
+    parity::{EvenParity, ParityScheme},
+    transfer::FrameTransfer,
+};
+
+impl<SPI, E, P> As5047d<SPI, P>
+where
+    SPI: FrameTransfer<Error = E>,
+    P: ParityScheme,
+{
+    /// Get an endless stream of angle samples
+    ///
+    /// Each poll of the stream performs a blocking [`Self::angle`] read, so
+    /// the sampling rate is determined by how fast the consumer polls plus
+    /// the SPI latency. The stream borrows the driver mutably for its lifetime
+    pub fn angle_stream(&mut self) -> AngleStream<'_, SPI, P> {
+        AngleStream { driver: self }
+    }
+}
+
+/// Endless stream of angle samples created by [`As5047d::angle_stream`]
+///
+/// The stream is not truly asynchronous: each poll performs a blocking SPI
+/// read on the bus and always returns [`Poll::Ready`], never yielding to the
+/// executor. Other tasks on the same executor do not run while the stream is
+/// polled in a loop, so pace the consumer (for example with an async timer)
+/// or use [`As5047d::angle`] directly outside of async code
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AngleStream<'a, SPI, P = EvenParity> {
+    driver: &'a mut As5047d<SPI, P>,
+}
+
+impl<SPI, E, P> Stream for AngleStream<'_, SPI, P>
+where
+    SPI: FrameTransfer<Error = E>,
+    P: ParityScheme,
+{
+    type Item = Result<u16, Error<E>>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(Some(self.get_mut().driver.angle()))
+    }
+}
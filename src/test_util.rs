@@ -0,0 +1,42 @@
+//! Frame encoding helpers for testing code that uses the driver
+//!
+//! These use the same encoding as the driver, so expectations built with
+//! them (for example with `embedded-hal-mock`) cannot drift from it
+
+use crate::{
+    parity::EvenParity,
+    protocol::{DATA_MASK, ERROR_FLAG, READ_BIT},
+    utils,
+};
+
+/// Build the bytes the sensor sends in response to a command
+///
+/// `data` is masked to 14 bits, the error flag is set if `error` is true, and
+/// the parity bit is set for even parity
+#[must_use]
+pub fn make_response_frame(data: u16, error: bool) -> [u8; 2] {
+    let frame = data & DATA_MASK;
+    let frame = if error { frame | ERROR_FLAG } else { frame };
+
+    utils::with_parity::<EvenParity>(frame).to_be_bytes()
+}
+
+/// Build the bytes the driver sends to read the register at `addr`
+#[must_use]
+pub fn make_read_command(addr: u16) -> [u8; 2] {
+    utils::with_parity::<EvenParity>(READ_BIT | (addr & DATA_MASK)).to_be_bytes()
+}
+
+/// Build the bytes the driver sends to write the register at `addr`
+#[must_use]
+pub fn make_write_command(addr: u16) -> [u8; 2] {
+    utils::with_parity::<EvenParity>(addr & DATA_MASK).to_be_bytes()
+}
+
+/// Build the bytes the driver sends as the data frame of a register write
+///
+/// `data` is masked to 14 bits and the parity bit is set for even parity
+#[must_use]
+pub fn make_write_data(data: u16) -> [u8; 2] {
+    utils::with_parity::<EvenParity>(data & DATA_MASK).to_be_bytes()
+}
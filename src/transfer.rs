@@ -0,0 +1,79 @@
+//! Transfer of single 16-bit frames over different SPI word sizes
+
+use embedded_hal::spi::{ErrorType, SpiDevice};
+
+use crate::driver::As5047d;
+
+/// Transfers a single 16-bit frame to the sensor within one transaction
+///
+/// This is implemented for every [`SpiDevice<u8>`], which sends the frame as
+/// two bytes, and for [`Word16`], which sends it as a single 16-bit word
+pub trait FrameTransfer {
+    /// Error type of the underlying SPI device
+    type Error;
+
+    /// Send `frame` and return the frame received at the same time
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the SPI transfer fails
+    fn transfer_frame(&mut self, frame: u16) -> Result<u16, Self::Error>;
+}
+
+impl<SPI> FrameTransfer for SPI
+where
+    SPI: SpiDevice<u8>,
+{
+    type Error = <SPI as ErrorType>::Error;
+
+    fn transfer_frame(&mut self, frame: u16) -> Result<u16, Self::Error> {
+        let tx = frame.to_be_bytes();
+        let mut rx = [0u8; 2];
+        self.transfer(&mut rx, &tx)?;
+
+        Ok(u16::from_be_bytes(rx))
+    }
+}
+
+impl<SPI> As5047d<Word16<SPI>>
+where
+    SPI: SpiDevice<u16>,
+{
+    /// Create a new AS5047D driver instance using 16-bit SPI words
+    pub fn new_word16(spi: SPI) -> Self {
+        Self::new(Word16::new(spi))
+    }
+}
+
+/// SPI device that transfers each frame as a single 16-bit word
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Word16<SPI> {
+    spi: SPI,
+}
+
+impl<SPI> Word16<SPI> {
+    /// Wrap an SPI device that supports 16-bit words
+    pub fn new(spi: SPI) -> Self {
+        Self { spi }
+    }
+
+    /// Release the SPI device
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+}
+
+impl<SPI> FrameTransfer for Word16<SPI>
+where
+    SPI: SpiDevice<u16>,
+{
+    type Error = SPI::Error;
+
+    fn transfer_frame(&mut self, frame: u16) -> Result<u16, Self::Error> {
+        let mut rx = [0u16; 1];
+        self.spi.transfer(&mut rx, &[frame])?;
+
+        Ok(rx[0])
+    }
+}
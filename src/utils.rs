@@ -1,10 +1,63 @@
+use crate::{
+    driver::ANGLE_MAX,
+    error::Error,
+    parity::ParityScheme,
+    protocol::{DATA_MASK, ERROR_FLAG, PARITY_BIT, READ_BIT},
+    register::Register,
+};
+
 /// Calculate even parity bit for the lower 15 bits of a 16-bit value
-pub fn calculate_parity(value: u16) -> bool {
+pub const fn calculate_parity(value: u16) -> bool {
     let bits = value & 0x7FFF;
     bits.count_ones() % 2 == 1
 }
 
 /// Verify even parity of a 16-bit frame
-pub fn verify_parity(frame: u16) -> bool {
+pub const fn verify_parity(frame: u16) -> bool {
     frame.count_ones().is_multiple_of(2)
 }
+
+/// Set the parity bit of a frame according to the parity scheme
+pub fn with_parity<P: ParityScheme>(frame: u16) -> u16 {
+    if P::calculate(frame) {
+        PARITY_BIT | frame
+    } else {
+        frame
+    }
+}
+
+/// Build the read command frame for a register
+pub fn read_command<P: ParityScheme>(register: Register) -> u16 {
+    with_parity::<P>(READ_BIT | register as u16)
+}
+
+/// Check the parity and error flag of a response frame and extract its data
+pub fn parse_response<P: ParityScheme, E>(response: u16) -> Result<u16, Error<E>> {
+    #[cfg(feature = "defmt")]
+    defmt::trace!("Received response: 0x{:04X}", response);
+
+    if !P::verify(response) {
+        #[cfg(feature = "defmt")]
+        defmt::warn!("Parity error in response: 0x{:04X}", response);
+        return Err(Error::ParityError);
+    }
+
+    if response & ERROR_FLAG != 0 {
+        #[cfg(feature = "defmt")]
+        defmt::warn!("Sensor error flag set in response");
+        return Err(Error::SensorError);
+    }
+
+    Ok(response & DATA_MASK)
+}
+
+/// Convert a 14-bit angle to degrees (0-359), rounded to the nearest degree
+///
+/// Angles that round up to 360° wrap to 0
+pub fn to_degrees(angle: u16) -> u16 {
+    let half = u64::from(ANGLE_MAX) / 2;
+    let degrees = (u64::from(angle & DATA_MASK) * 360 + half) / u64::from(ANGLE_MAX) % 360;
+    #[allow(clippy::cast_possible_truncation)]
+    let degrees = degrees as u16;
+    degrees
+}
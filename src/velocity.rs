@@ -0,0 +1,73 @@
+//! Angular velocity estimation from timestamped angle readings
+
+use crate::angle;
+#[cfg(feature = "float")]
+use crate::driver::ANGLE_MAX;
+
+/// Estimates angular velocity from successive 14-bit angle readings
+///
+/// Timestamps are supplied by the caller, so any timer source can be used
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VelocityTracker {
+    last: Option<(u16, u64)>,
+    velocity: i32,
+}
+
+impl VelocityTracker {
+    /// Create a new velocity tracker
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            last: None,
+            velocity: 0,
+        }
+    }
+
+    /// Update the tracker with a new angle reading taken at `now_micros`
+    ///
+    /// Returns the signed velocity in counts per second, using the shortest
+    /// arc between the previous and current reading. The first update
+    /// returns 0. If no time has elapsed since the previous update, the
+    /// previous velocity is returned
+    pub fn update(&mut self, raw_angle: u16, now_micros: u64) -> i32 {
+        let Some((last_angle, last_micros)) = self.last else {
+            self.last = Some((raw_angle, now_micros));
+            return 0;
+        };
+
+        let elapsed = now_micros.wrapping_sub(last_micros);
+        if elapsed == 0 {
+            return self.velocity;
+        }
+
+        let delta = i64::from(angle::shortest_delta(last_angle, raw_angle));
+        let velocity = delta * 1_000_000 / i64::try_from(elapsed).unwrap_or(i64::MAX);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let velocity = velocity.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+
+        self.last = Some((raw_angle, now_micros));
+        self.velocity = velocity;
+
+        velocity
+    }
+
+    /// Get the most recently computed velocity in counts per second
+    #[must_use]
+    pub fn velocity(&self) -> i32 {
+        self.velocity
+    }
+
+    /// Get the most recently computed velocity in revolutions per minute
+    ///
+    /// Positive values correspond to increasing raw angle counts and negative
+    /// values to decreasing counts
+    #[cfg(feature = "float")]
+    #[must_use]
+    pub fn rpm(&self) -> f32 {
+        #[allow(clippy::cast_precision_loss)]
+        let velocity = self.velocity as f32;
+        velocity / f32::from(ANGLE_MAX) * 60.0
+    }
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "otp-programming")]
+
+use as5047d::{As5047d, Error, Register, test_util::make_response_frame};
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, Transaction as DelayTransaction},
+    spi::{Mock, Transaction},
+};
+
+mod common;
+
+const POLL_INTERVAL_US: u32 = 100;
+
+const REGISTERS: [(Register, u16); 4] = [
+    (Register::ZPosM, 0x0012),
+    (Register::ZPosL, 0x0005),
+    (Register::Settings1, 0x0001),
+    (Register::Settings2, 0x0020),
+];
+
+fn read(expectations: &mut Vec<Transaction<u8>>, register: Register, value: u16) {
+    expectations.extend(common::read(register, make_response_frame(value, false)));
+}
+
+fn write(expectations: &mut Vec<Transaction<u8>>, register: Register, value: u16) {
+    expectations.extend(common::write(
+        register,
+        value,
+        make_response_frame(value, false),
+    ));
+}
+
+/// Expect the burn to be started, up to the first completion poll
+fn start_burn(expectations: &mut Vec<Transaction<u8>>) {
+    for (register, value) in REGISTERS {
+        read(expectations, register, value);
+    }
+
+    write(expectations, Register::Prog, 0x01);
+    for (register, value) in REGISTERS {
+        write(expectations, register, value);
+    }
+    write(expectations, Register::Prog, 0x08);
+}
+
+#[test]
+fn burn_otp_frame_sequence() {
+    let mut expectations = Vec::new();
+    start_burn(&mut expectations);
+
+    read(&mut expectations, Register::Prog, 0x08);
+    read(&mut expectations, Register::Prog, 0x01);
+
+    for (register, _) in REGISTERS {
+        write(&mut expectations, register, 0);
+    }
+    write(&mut expectations, Register::Prog, 0x40);
+    write(&mut expectations, Register::Prog, 0x04);
+    for (register, value) in REGISTERS {
+        read(&mut expectations, register, value);
+    }
+
+    let mut spi = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&[DelayTransaction::delay_us(POLL_INTERVAL_US)]);
+    let mut sensor = As5047d::new(spi.clone());
+
+    assert!(sensor.burn_otp(&mut delay, POLL_INTERVAL_US, 10).is_ok());
+
+    spi.done();
+    delay.done();
+}
+
+#[test]
+fn burn_otp_timeout() {
+    let mut expectations = Vec::new();
+    start_burn(&mut expectations);
+
+    for _ in 0..3 {
+        read(&mut expectations, Register::Prog, 0x08);
+    }
+
+    let mut spi = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&[
+        DelayTransaction::delay_us(POLL_INTERVAL_US),
+        DelayTransaction::delay_us(POLL_INTERVAL_US),
+    ]);
+    let mut sensor = As5047d::new(spi.clone());
+
+    assert!(matches!(
+        sensor.burn_otp(&mut delay, POLL_INTERVAL_US, 3),
+        Err(Error::Timeout)
+    ));
+
+    spi.done();
+    delay.done();
+}
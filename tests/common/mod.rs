@@ -0,0 +1,37 @@
+//! Mock expectations shared by the integration tests
+
+#![allow(dead_code)]
+
+use as5047d::{
+    Register,
+    test_util::{make_read_command, make_write_command, make_write_data},
+};
+use embedded_hal_mock::eh1::spi::Transaction;
+
+/// NOP command frame, and the response of the NOP register
+pub const NOP: [u8; 2] = [0x00, 0x00];
+
+/// Expect a single 16-bit frame transfer
+pub fn frame(tx: [u8; 2], rx: [u8; 2]) -> [Transaction<u8>; 3] {
+    [
+        Transaction::transaction_start(),
+        Transaction::transfer(tx.to_vec(), rx.to_vec()),
+        Transaction::transaction_end(),
+    ]
+}
+
+/// Expect a read of `register` answered with `response`
+pub fn read(register: Register, response: [u8; 2]) -> Vec<Transaction<u8>> {
+    let mut expectations = frame(make_read_command(register.into()), NOP).to_vec();
+    expectations.extend(frame(NOP, response));
+    expectations
+}
+
+/// Expect a write of `data` to `register`, with `response` to the
+/// verification frame
+pub fn write(register: Register, data: u16, response: [u8; 2]) -> Vec<Transaction<u8>> {
+    let mut expectations = frame(make_write_command(register.into()), NOP).to_vec();
+    expectations.extend(frame(make_write_data(data), NOP));
+    expectations.extend(frame(NOP, response));
+    expectations
+}
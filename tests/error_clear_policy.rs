@@ -0,0 +1,66 @@
+use as5047d::{
+    As5047d, Error, ErrorClearPolicy, Register,
+    test_util::{make_read_command, make_response_frame},
+};
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+mod common;
+
+use common::{NOP, frame, read};
+
+/// Expect a read of the clear error flag register
+fn clear_error_flag() -> Vec<Transaction<u8>> {
+    read(Register::ErrFl, make_response_frame(0x0001, false))
+}
+
+#[test]
+fn pipelined_read_clears_error_flag_always() {
+    let angle = make_read_command(Register::AngleCom.into());
+    let diagnostics = make_read_command(Register::DiaAgc.into());
+
+    let mut expectations = Vec::new();
+    expectations.extend(frame(angle, NOP));
+    expectations.extend(frame(diagnostics, make_response_frame(0, true)));
+    expectations.extend(frame(NOP, make_response_frame(0x0180, false)));
+    expectations.extend(clear_error_flag());
+
+    let mut spi = Mock::new(&expectations);
+    let mut sensor = As5047d::builder(spi.clone())
+        .error_clear_policy(ErrorClearPolicy::Always)
+        .build();
+
+    assert!(matches!(
+        sensor.angle_with_diagnostics(),
+        Err(Error::SensorError)
+    ));
+
+    spi.done();
+}
+
+#[test]
+fn pipelined_read_clears_error_flag_every_n() {
+    let angle = make_read_command(Register::AngleCom.into());
+
+    let mut expectations = Vec::new();
+    expectations.extend(frame(angle, NOP));
+    expectations.extend(frame(angle, make_response_frame(0, true)));
+    expectations.extend(frame(angle, make_response_frame(0, true)));
+    expectations.extend(clear_error_flag());
+    // The clear leaves the NOP register addressed, so the next read primes
+    // again instead of returning the NOP data as an angle
+    expectations.extend(frame(angle, NOP));
+    expectations.extend(frame(angle, make_response_frame(0x1234, false)));
+
+    let mut spi = Mock::new(&expectations);
+    let mut sensor = As5047d::builder(spi.clone())
+        .error_clear_policy(ErrorClearPolicy::EveryN(2))
+        .build();
+
+    for _ in 0..2 {
+        assert!(matches!(sensor.angle_pipelined(), Err(Error::SensorError)));
+    }
+    assert_eq!(sensor.angle_pipelined().ok(), Some(0x1234));
+    assert_eq!(sensor.last_angle(), Some(0x1234));
+
+    spi.done();
+}
@@ -0,0 +1,32 @@
+use as5047d::{
+    As5047d, Register,
+    test_util::{make_read_command, make_response_frame},
+};
+use embedded_hal_mock::eh1::spi::Mock;
+
+mod common;
+
+use common::{NOP, frame};
+
+#[test]
+fn reversed_agc_bounds_are_swapped() {
+    // Offset compensation finished, AGC value 150
+    let diagnostics = 0x0100 | 150;
+    let angle = 0x1000;
+
+    let mut expectations = Vec::new();
+    expectations.extend(frame(make_read_command(Register::DiaAgc.into()), NOP));
+    expectations.extend(frame(
+        make_read_command(Register::AngleCom.into()),
+        make_response_frame(diagnostics, false),
+    ));
+    expectations.extend(frame(NOP, make_response_frame(angle, false)));
+
+    let mut spi = Mock::new(&expectations);
+    let mut sensor = As5047d::new(spi.clone());
+    sensor.set_field_thresholds(0, 200, 100);
+
+    assert_eq!(sensor.angle_if_valid().ok(), Some(Some(angle)));
+
+    spi.done();
+}
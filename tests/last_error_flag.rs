@@ -0,0 +1,48 @@
+use as5047d::{As5047d, Error, Register, test_util::make_response_frame};
+use embedded_hal_mock::eh1::spi::Mock;
+
+mod common;
+
+use common::{read, write};
+
+#[test]
+fn write_register_records_error_flag() {
+    let mut expectations = write(Register::Settings1, 0x0005, make_response_frame(0, true));
+    expectations.extend(write(
+        Register::Settings1,
+        0x0005,
+        make_response_frame(0x0005, false),
+    ));
+
+    let mut spi = Mock::new(&expectations);
+    let mut sensor = As5047d::new(spi.clone());
+
+    assert!(matches!(
+        sensor.write_register(Register::Settings1, 0x0005),
+        Err(Error::SensorError)
+    ));
+    assert!(sensor.last_error_flag());
+
+    assert!(sensor.write_register(Register::Settings1, 0x0005).is_ok());
+    assert!(!sensor.last_error_flag());
+
+    spi.done();
+}
+
+#[test]
+fn raw_reads_record_error_flag() {
+    let mut expectations = read(Register::DiaAgc, make_response_frame(0, true));
+    expectations.extend(read(Register::DiaAgc, make_response_frame(0, false)));
+
+    let mut spi = Mock::new(&expectations);
+    let mut sensor = As5047d::new(spi.clone());
+
+    assert!(sensor.read_frame(Register::DiaAgc).is_ok());
+    assert!(sensor.last_error_flag());
+
+    let command = 0x4000 | u16::from(Register::DiaAgc);
+    assert!(sensor.transfer_raw(command).is_ok());
+    assert!(!sensor.last_error_flag());
+
+    spi.done();
+}
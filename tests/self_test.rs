@@ -0,0 +1,53 @@
+use as5047d::{As5047d, Error, Register, test_util::make_response_frame};
+use embedded_hal_mock::eh1::spi::Mock;
+
+mod common;
+
+use common::{NOP, read};
+
+#[test]
+fn self_test_passes_on_zero_frames() {
+    let mut expectations = Vec::new();
+    for _ in 0..4 {
+        expectations.extend(read(Register::Nop, NOP));
+    }
+
+    let mut spi = Mock::new(&expectations);
+    let mut sensor = As5047d::new(spi.clone());
+
+    assert!(sensor.self_test().is_ok());
+
+    spi.done();
+}
+
+#[test]
+fn self_test_reports_sensor_error() {
+    let mut expectations = Vec::new();
+    expectations.extend(read(Register::Nop, make_response_frame(0, true)));
+
+    let mut spi = Mock::new(&expectations);
+    let mut sensor = As5047d::new(spi.clone());
+
+    assert!(matches!(sensor.self_test(), Err(Error::SensorError)));
+    assert!(sensor.last_error_flag());
+
+    spi.done();
+}
+
+#[test]
+fn self_test_reports_unexpected_response() {
+    let response = make_response_frame(0x0123, false);
+    let mut expectations = Vec::new();
+    expectations.extend(read(Register::Nop, NOP));
+    expectations.extend(read(Register::Nop, response));
+
+    let mut spi = Mock::new(&expectations);
+    let mut sensor = As5047d::new(spi.clone());
+
+    assert!(matches!(
+        sensor.self_test(),
+        Err(Error::UnexpectedResponse(frame)) if frame == u16::from_be_bytes(response)
+    ));
+
+    spi.done();
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "serde")]
+
+use as5047d::angle::{Degrees, RawAngle};
+
+#[test]
+fn raw_angle_round_trip() {
+    let angle = RawAngle::new(12345);
+    let json = serde_json::to_string(&angle).unwrap();
+    assert_eq!(json, "12345");
+    assert_eq!(serde_json::from_str::<RawAngle>(&json).unwrap(), angle);
+}
+
+#[test]
+fn raw_angle_out_of_range_is_wrapped() {
+    let angle = serde_json::from_str::<RawAngle>("16500").unwrap();
+    assert_eq!(angle, RawAngle::new(16500));
+    assert_eq!(angle.get(), 116);
+}
+
+#[test]
+fn degrees_round_trip() {
+    let degrees = Degrees::new(270);
+    let json = serde_json::to_string(&degrees).unwrap();
+    assert_eq!(json, "270");
+    assert_eq!(serde_json::from_str::<Degrees>(&json).unwrap(), degrees);
+}
+
+#[test]
+fn degrees_out_of_range_is_wrapped() {
+    let degrees = serde_json::from_str::<Degrees>("60000").unwrap();
+    assert_eq!(degrees.get(), 60000 % 360);
+    assert_eq!(degrees + Degrees::new(359), Degrees::new(60000 + 359));
+}
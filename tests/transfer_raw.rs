@@ -0,0 +1,29 @@
+use as5047d::{As5047d, Error, Register};
+use embedded_hal_mock::eh1::spi::Mock;
+
+mod common;
+
+use common::read;
+
+/// Response frame with odd parity
+const BAD_PARITY: [u8; 2] = [0x00, 0x01];
+
+#[test]
+fn transfer_raw_honors_parity_checking() {
+    let command = 0x4000 | u16::from(Register::DiaAgc);
+    let mut expectations = read(Register::DiaAgc, BAD_PARITY);
+    expectations.extend(read(Register::DiaAgc, BAD_PARITY));
+
+    let mut spi = Mock::new(&expectations);
+    let mut sensor = As5047d::new(spi.clone());
+
+    assert!(matches!(
+        sensor.transfer_raw(command),
+        Err(Error::ParityError)
+    ));
+
+    sensor.set_parity_checking(false);
+    assert_eq!(sensor.transfer_raw(command).ok(), Some(0x0001));
+
+    spi.done();
+}